@@ -1,4 +1,9 @@
-advent_of_code::solution!(2);
+use advent_of_code::{
+    parse::{self, ParseError},
+    parse_line,
+};
+
+advent_of_code::solution!(2, parse = parse_input);
 
 #[derive(Debug, PartialEq)]
 struct BoxDims {
@@ -8,13 +13,9 @@ struct BoxDims {
 }
 
 impl BoxDims {
-    fn from_input(dims: &str) -> Self {
-        let mut parsed = dims.split("x").map(|v| str::parse(v).unwrap());
-        Self {
-            w: parsed.next().unwrap(),
-            h: parsed.next().unwrap(),
-            l: parsed.next().unwrap(),
-        }
+    fn from_input(dims: &str) -> Result<Self, ParseError> {
+        let (w, h, l) = parse_line!("{}x{}x{}", dims => u32, u32, u32)?;
+        Ok(Self { w, h, l })
     }
 
     fn required_wrapping(&self) -> u32 {
@@ -33,22 +34,22 @@ impl BoxDims {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .split_whitespace()
-            .map(|i| BoxDims::from_input(i).required_wrapping())
-            .sum(),
-    )
+fn parse_input(input: &str) -> Result<Vec<BoxDims>, ParseError> {
+    parse::lines(input, BoxDims::from_input)
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    Ok(parse_input(input)?
+        .iter()
+        .map(BoxDims::required_wrapping)
+        .sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        input
-            .split_whitespace()
-            .map(|i| BoxDims::from_input(i).required_ribbon())
-            .sum(),
-    )
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    Ok(parse_input(input)?
+        .iter()
+        .map(BoxDims::required_ribbon)
+        .sum())
 }
 
 #[cfg(test)]
@@ -60,19 +61,32 @@ mod tests {
     #[case("2x3x4", BoxDims { w: 2, h: 3, l: 4})]
     #[case("1x1x10", BoxDims { w: 1, h: 1, l: 10})]
     fn test_box_dims(#[case] input: &str, #[case] expected: BoxDims) {
-        assert_eq!(BoxDims::from_input(input), expected);
+        assert_eq!(BoxDims::from_input(input), Ok(expected));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let error = part_one("2x3x4\n1x1\n").unwrap_err();
+        assert_eq!(error.line_number, Some(2));
+        assert_eq!(error.expected, "`x`");
     }
 
     #[rstest]
     #[case("2x3x4", 58)]
     #[case("1x1x10", 43)]
     fn test_required_wrapping(#[case] input: &str, #[case] expected: u32) {
-        assert_eq!(BoxDims::from_input(input).required_wrapping(), expected);
+        assert_eq!(
+            BoxDims::from_input(input).unwrap().required_wrapping(),
+            expected
+        );
     }
     #[rstest]
     #[case("2x3x4", 34)]
     #[case("1x1x10", 14)]
     fn test_required_ribbon(#[case] input: &str, #[case] expected: u32) {
-        assert_eq!(BoxDims::from_input(input).required_ribbon(), expected);
+        assert_eq!(
+            BoxDims::from_input(input).unwrap().required_ribbon(),
+            expected
+        );
     }
 }
@@ -1,61 +1,23 @@
 use std::collections::HashSet;
-use std::ops::{Add, AddAssign};
 
-advent_of_code::solution!(3);
-
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-impl Coord {
-    fn origin() -> Self {
-        Self { x: 0, y: 0 }
-    }
-}
+use advent_of_code::point::{Direction, Point2};
 
-impl From<char> for Coord {
-    fn from(value: char) -> Self {
-        match value {
-            '<' => Self { x: -1, y: 0 },
-            '>' => Self { x: 1, y: 0 },
-            '^' => Self { x: 0, y: 1 },
-            'v' => Self { x: 0, y: -1 },
-            _ => Self::origin(),
-        }
-    }
-}
+advent_of_code::solution!(3);
 
-impl Add for Coord {
-    type Output = Self;
+type Coord = Point2<i32>;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-impl AddAssign for Coord {
-    fn add_assign(&mut self, other: Self) {
-        *self = Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        };
-    }
+fn directions(input: &str) -> impl Iterator<Item = Direction> + '_ {
+    input.chars().filter_map(|c| Direction::try_from(c).ok())
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
-        input
-            .chars()
-            .map(Coord::from)
-            .scan(Coord::origin(), |santa_pos, instruction| {
+        directions(input)
+            .scan(Coord::default(), |santa_pos, instruction| {
                 *santa_pos += instruction;
                 Some(*santa_pos)
             })
-            .chain([Coord::origin()])
+            .chain([Coord::default()])
             .collect::<HashSet<_>>()
             .len() as u32,
     )
@@ -63,11 +25,10 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     Some(
-        input
-            .char_indices()
-            .map(|(i, c)| (i, Coord::from(c)))
+        directions(input)
+            .enumerate()
             .scan(
-                (Coord::origin(), Coord::origin()),
+                (Coord::default(), Coord::default()),
                 |(santa_pos, robo_santa_pos), (index, instruction)| {
                     match index % 2 {
                         0 => *santa_pos += instruction,
@@ -78,7 +39,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     Some([*santa_pos, *robo_santa_pos])
                 },
             )
-            .chain([[Coord::origin(), Coord::origin()]])
+            .chain([[Coord::default(), Coord::default()]])
             .flatten()
             .collect::<HashSet<_>>()
             .len() as u32,
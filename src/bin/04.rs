@@ -1,28 +1,47 @@
-use ::md5;
-use md5::Digest;
+use std::io::Write;
+
+use advent_of_code::parallel::par_find_first_with;
+use md5::{Context, Digest};
 
 advent_of_code::solution!(4);
 
-fn advent_coin_miner(input: &str, condition: fn(hash: Digest) -> bool) -> Option<u32> {
-    (1..).find(|num| {
-        let mut val = input.trim().to_string();
-        val.push_str(&num.to_string());
+/// Numbers each thread checks before taking the next chunk.
+const CHUNK: u64 = 4096;
+
+/// Whether the hex representation of `hash` starts with `zeros` zeros.
+fn has_leading_zero_nibbles(hash: &Digest, zeros: usize) -> bool {
+    let (bytes, odd) = (zeros / 2, zeros % 2 == 1);
+    hash.0[..bytes].iter().all(|byte| *byte == 0) && (!odd || hash.0[bytes] >> 4 == 0)
+}
+
+/// Smallest positive number whose hash, appended to the secret key, starts with `zeros` zeros in hex.
+fn advent_coin_miner(input: &str, zeros: usize) -> Option<u32> {
+    // the key is hashed once, every candidate only hashes its digits on top.
+    let mut key = Context::new();
+    key.consume(input.trim());
 
-        let hash = md5::compute(val);
-        condition(hash)
-    })
+    par_find_first_with(
+        1..=u64::from(u32::MAX),
+        CHUNK,
+        || Vec::with_capacity(20),
+        |digits, num| {
+            digits.clear();
+            write!(digits, "{num}").unwrap();
+
+            let mut context = key.clone();
+            context.consume(&digits);
+            has_leading_zero_nibbles(&context.compute(), zeros)
+        },
+    )
+    .and_then(|num| u32::try_from(num).ok())
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    advent_coin_miner(input, |hash| {
-        (hash.0[0] == 0u8) && (hash.0[1] == 0u8) && (hash.0[2] | 0b0000_1111 == 0b0000_1111)
-    })
+    advent_coin_miner(input, 5)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    advent_coin_miner(input, |hash| {
-        (hash.0[0] == 0u8) && (hash.0[1] == 0u8) && (hash.0[2] == 0u8)
-    })
+    advent_coin_miner(input, 6)
 }
 
 #[cfg(test)]
@@ -37,4 +56,20 @@ mod tests {
         let result = part_one(input);
         assert_eq!(result, Some(expected));
     }
+
+    #[rstest]
+    #[case([0x00, 0x00, 0x0f], 5, true)]
+    #[case([0x00, 0x00, 0x10], 5, false)]
+    #[case([0x00, 0x00, 0x00], 6, true)]
+    #[case([0x00, 0x00, 0x01], 6, false)]
+    #[case([0x01, 0x00, 0x00], 0, true)]
+    fn test_has_leading_zero_nibbles(
+        #[case] start: [u8; 3],
+        #[case] zeros: usize,
+        #[case] expected: bool,
+    ) {
+        let mut hash = [0xff; 16];
+        hash[..3].copy_from_slice(&start);
+        assert_eq!(has_leading_zero_nibbles(&Digest(hash), zeros), expected);
+    }
 }
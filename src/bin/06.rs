@@ -1,50 +1,55 @@
+use advent_of_code::{
+    grid::Grid,
+    parse::{self, ParseError},
+    parse_line,
+};
+
 advent_of_code::solution!(6);
 
 struct Lights {
-    lights: [[u32; 1000]; 1000],
+    lights: Grid<u32>,
 }
 
 impl Lights {
     fn new() -> Self {
         Self {
-            lights: [[0; 1000]; 1000],
+            lights: Grid::new(1000, 1000, 0),
         }
     }
 
+    fn region(&mut self, instruction: &Instruction) -> impl Iterator<Item = &mut u32> {
+        self.lights
+            .region_mut(
+                instruction.from.0..=instruction.to.0,
+                instruction.from.1..=instruction.to.1,
+            )
+            .map(|(_, light)| light)
+    }
+
     fn apply_instruction_p1(&mut self, instruction: Instruction) {
-        (instruction.from.0..=instruction.to.0).for_each(|i| {
-            (instruction.from.1..=instruction.to.1).for_each(|j| match instruction.kind {
-                InstructionKind::TurnOff => self.lights[i][j] = 0,
-                InstructionKind::TurnOn => self.lights[i][j] = 1,
-                InstructionKind::Toggle => {
-                    self.lights[i][j] = if self.lights[i][j] == 0 { 1 } else { 0 }
-                }
+        self.region(&instruction)
+            .for_each(|light| match instruction.kind {
+                InstructionKind::TurnOff => *light = 0,
+                InstructionKind::TurnOn => *light = 1,
+                InstructionKind::Toggle => *light = if *light == 0 { 1 } else { 0 },
             });
-        });
     }
 
     fn apply_instruction_p2(&mut self, instruction: Instruction) {
-        (instruction.from.0..=instruction.to.0).for_each(|i| {
-            (instruction.from.1..=instruction.to.1).for_each(|j| match instruction.kind {
-                InstructionKind::TurnOff => {
-                    self.lights[i][j] = 0.max((self.lights[i][j] as i64) - 1) as u32
-                }
-                InstructionKind::TurnOn => self.lights[i][j] += 1,
-                InstructionKind::Toggle => self.lights[i][j] += 2,
+        self.region(&instruction)
+            .for_each(|light| match instruction.kind {
+                InstructionKind::TurnOff => *light = light.saturating_sub(1),
+                InstructionKind::TurnOn => *light += 1,
+                InstructionKind::Toggle => *light += 2,
             });
-        });
     }
 
     fn count_on(&self) -> usize {
-        self.lights
-            .as_flattened()
-            .iter()
-            .filter(|&&l| l > 0)
-            .count()
+        self.lights.cells().iter().filter(|&&l| l > 0).count()
     }
 
     fn total_brightness(&self) -> u32 {
-        self.lights.as_flattened().iter().sum()
+        self.lights.cells().iter().sum()
     }
 }
 
@@ -60,51 +65,54 @@ struct Instruction {
     to: (usize, usize),
 }
 
-impl<'a> From<&'a str> for Instruction {
-    fn from(value: &'a str) -> Self {
-        let kind: InstructionKind;
-
-        let range_part: &'a str;
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
 
-        if value.starts_with("turn on") {
-            kind = InstructionKind::TurnOn;
-            range_part = value.strip_prefix("turn on ").unwrap();
-        } else if value.starts_with("turn off") {
-            kind = InstructionKind::TurnOff;
-            range_part = value.strip_prefix("turn off ").unwrap();
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (kind, (from_x, from_y, to_x, to_y)) = if value.starts_with("turn on ") {
+            (
+                InstructionKind::TurnOn,
+                parse_line!("turn on {},{} through {},{}", value => usize, usize, usize, usize)?,
+            )
+        } else if value.starts_with("turn off ") {
+            (
+                InstructionKind::TurnOff,
+                parse_line!("turn off {},{} through {},{}", value => usize, usize, usize, usize)?,
+            )
+        } else if value.starts_with("toggle ") {
+            (
+                InstructionKind::Toggle,
+                parse_line!("toggle {},{} through {},{}", value => usize, usize, usize, usize)?,
+            )
         } else {
-            kind = InstructionKind::Toggle;
-            range_part = value.strip_prefix("toggle ").unwrap();
-        }
-
-        let mut i = range_part.split(" through ");
-        let from_part = i.next().unwrap().split_once(",").unwrap();
-        let from: (usize, usize) = (
-            str::parse(from_part.0).unwrap(),
-            str::parse(from_part.1).unwrap(),
-        );
-        let to_part = i.next().unwrap().split_once(",").unwrap();
-        let to: (usize, usize) = (
-            str::parse(to_part.0).unwrap(),
-            str::parse(to_part.1).unwrap(),
-        );
+            let word = value.split(' ').next().unwrap_or_default();
+            return Err(ParseError::at(
+                value,
+                word,
+                "`turn on`, `turn off` or `toggle`",
+            ));
+        };
 
-        Self { kind, from, to }
+        Ok(Self {
+            kind,
+            from: (from_x, from_y),
+            to: (to_x, to_y),
+        })
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
     let mut lights = Lights::new();
-    input.lines().for_each(|line| {
-        lights.apply_instruction_p1(Instruction::from(line));
-    });
-    Some(lights.count_on())
+    parse::lines(input, Instruction::try_from)?
+        .into_iter()
+        .for_each(|instruction| lights.apply_instruction_p1(instruction));
+    Ok(lights.count_on())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
     let mut lights = Lights::new();
-    input.lines().for_each(|line| {
-        lights.apply_instruction_p2(Instruction::from(line));
-    });
-    Some(lights.total_brightness())
+    parse::lines(input, Instruction::try_from)?
+        .into_iter()
+        .for_each(|instruction| lights.apply_instruction_p2(instruction));
+    Ok(lights.total_brightness())
 }
@@ -1,11 +1,17 @@
 advent_of_code::solution!(7);
 
-use std::collections::HashMap;
+use std::error::Error;
+
+use advent_of_code::{
+    intern::{Interner, Symbol},
+    parse::{self, FromField, ParseError},
+    parse_line,
+};
 
 #[derive(Debug)]
 enum ComponentInput {
     Signal(u16),
-    Component(String),
+    Component(Symbol),
 }
 
 #[derive(Debug)]
@@ -18,7 +24,8 @@ enum CircuitComponent {
     Not(ComponentInput),
 }
 
-type ResCache = HashMap<String, Option<u16>>;
+/// Resolved signal of each wire, indexed by its id. `None` if the wire was not resolved yet.
+type ResCache = Vec<Option<Option<u16>>>;
 
 fn get_component_input_value(
     value: &ComponentInput,
@@ -27,7 +34,7 @@ fn get_component_input_value(
 ) -> Option<u16> {
     match value {
         ComponentInput::Signal(value) => Some(*value),
-        ComponentInput::Component(key) => board.get_signal(key, resolution_cache),
+        ComponentInput::Component(wire_id) => board.get_signal(*wire_id, resolution_cache),
     }
 }
 
@@ -78,84 +85,120 @@ impl CircuitComponent {
 }
 
 struct CircuitBoard {
-    components: HashMap<String, CircuitComponent>,
+    wires: Interner,
+    /// Component that drives each wire, indexed by its id.
+    components: Vec<Option<CircuitComponent>>,
 }
 
 impl CircuitBoard {
-    fn get_signal(&self, wire_id: &String, resolution_cache: &mut ResCache) -> Option<u16> {
-        match resolution_cache.get(wire_id).cloned() {
+    fn get_signal(&self, wire_id: Symbol, resolution_cache: &mut ResCache) -> Option<u16> {
+        match resolution_cache[wire_id.index()] {
             Some(val) => val,
             None => {
-                let res = self
-                    .components
-                    .get(wire_id)
+                let res = self.components[wire_id.index()]
+                    .as_ref()
                     .map(|c| c.resolve(self, resolution_cache));
-                resolution_cache.insert(wire_id.to_string(), res);
+                resolution_cache[wire_id.index()] = Some(res);
                 res
             }
         }
     }
 
-    fn from_instructions(instructions: &str) -> Self {
-        Self {
-            components: HashMap::from_iter(instructions.lines().map(parse_instruction)),
-        }
+    fn get_signal_by_name(&self, wire: &str, resolution_cache: &mut ResCache) -> Option<u16> {
+        self.wires
+            .get(wire)
+            .and_then(|wire_id| self.get_signal(wire_id, resolution_cache))
     }
-}
 
-fn parse_source(soure_str: &str) -> ComponentInput {
-    match soure_str.parse::<u16>() {
-        Ok(value) => ComponentInput::Signal(value),
-        Err(_) => ComponentInput::Component(soure_str.to_string()),
+    fn new_cache(&self) -> ResCache {
+        vec![None; self.components.len()]
+    }
+
+    fn from_instructions(instructions: &str) -> Result<Self, ParseError> {
+        let mut wires = Interner::new();
+        let mut components = vec![];
+
+        parse::lines(instructions, |instruction| {
+            let (target, component) = parse_instruction(instruction, &mut wires)?;
+            components.resize_with(wires.len(), || None);
+            components[target.index()] = Some(component);
+            Ok(())
+        })?;
+
+        Ok(Self { wires, components })
     }
 }
 
-fn parse_component(component_str: &str) -> CircuitComponent {
-    if component_str.contains(" AND ") {
-        let (input_l, input_r) = component_str.split_once(" AND ").unwrap();
-        CircuitComponent::AndGate(parse_source(input_l), parse_source(input_r))
-    } else if component_str.contains(" OR ") {
-        let (input_l, input_r) = component_str.split_once(" OR ").unwrap();
-        CircuitComponent::OrGate(parse_source(input_l), parse_source(input_r))
-    } else if component_str.contains(" LSHIFT ") {
-        let (input, by_str) = component_str.split_once(" LSHIFT ").unwrap();
-        let by = by_str.parse::<usize>().unwrap();
-        CircuitComponent::LeftShifter(parse_source(input), by)
-    } else if component_str.contains(" RSHIFT ") {
-        let (input, by_str) = component_str.split_once(" RSHIFT ").unwrap();
-        let by = by_str.parse::<usize>().unwrap();
-        CircuitComponent::RightShifter(parse_source(input), by)
-    } else if component_str.contains("NOT ") {
-        let input = component_str.strip_prefix("NOT ").unwrap();
-        CircuitComponent::Not(parse_source(input))
-    } else {
-        CircuitComponent::Direct(parse_source(component_str))
+/// A signal or the name of a wire, which is interned.
+impl FromField for ComponentInput {
+    fn from_field(field: &str, wires: &mut Interner) -> Option<Self> {
+        match field.parse::<u16>() {
+            Ok(value) => Some(ComponentInput::Signal(value)),
+            Err(_) => Symbol::from_field(field, wires).map(ComponentInput::Component),
+        }
     }
 }
 
-fn parse_instruction(instruction: &str) -> (String, CircuitComponent) {
-    let (instruction_part, target) = instruction.split_once(" -> ").unwrap();
+fn parse_instruction(
+    instruction: &str,
+    wires: &mut Interner,
+) -> Result<(Symbol, CircuitComponent), ParseError> {
+    let (component, target) = if instruction.contains(" AND ") {
+        let (l, r, target) = parse_line!(
+            "{} AND {} -> {}",
+            instruction, wires => ComponentInput, ComponentInput, Symbol
+        )?;
+        (CircuitComponent::AndGate(l, r), target)
+    } else if instruction.contains(" OR ") {
+        let (l, r, target) = parse_line!(
+            "{} OR {} -> {}",
+            instruction, wires => ComponentInput, ComponentInput, Symbol
+        )?;
+        (CircuitComponent::OrGate(l, r), target)
+    } else if instruction.contains(" LSHIFT ") {
+        let (input, by, target) =
+            parse_line!("{} LSHIFT {} -> {}", instruction, wires => ComponentInput, usize, Symbol)?;
+        (CircuitComponent::LeftShifter(input, by), target)
+    } else if instruction.contains(" RSHIFT ") {
+        let (input, by, target) =
+            parse_line!("{} RSHIFT {} -> {}", instruction, wires => ComponentInput, usize, Symbol)?;
+        (CircuitComponent::RightShifter(input, by), target)
+    } else if instruction.starts_with("NOT ") {
+        let (input, target) =
+            parse_line!("NOT {} -> {}", instruction, wires => ComponentInput, Symbol)?;
+        (CircuitComponent::Not(input), target)
+    } else {
+        let (input, target) =
+            parse_line!("{} -> {}", instruction, wires => ComponentInput, Symbol)?;
+        (CircuitComponent::Direct(input), target)
+    };
 
-    (target.to_string(), parse_component(instruction_part))
+    Ok((target, component))
 }
 
-pub fn part_one(input: &str) -> Option<u16> {
-    let mut cache = ResCache::new();
-    CircuitBoard::from_instructions(input).get_signal(&"a".to_string(), &mut cache)
+pub fn part_one(input: &str) -> Result<u16, Box<dyn Error>> {
+    let board = CircuitBoard::from_instructions(input)?;
+    let mut cache = board.new_cache();
+    Ok(board
+        .get_signal_by_name("a", &mut cache)
+        .ok_or("no signal reaches wire `a`")?)
 }
 
-pub fn part_two(input: &str) -> Option<u16> {
-    let mut board = CircuitBoard::from_instructions(input);
-    let mut cache = ResCache::new();
-
-    let original_a = board.get_signal(&"a".to_string(), &mut cache).unwrap();
-    board.components.insert(
-        "b".to_string(),
-        CircuitComponent::Direct(ComponentInput::Signal(original_a)),
-    );
-
-    cache.clear();
-    board.get_signal(&"a".to_string(), &mut cache)
+pub fn part_two(input: &str) -> Result<u16, Box<dyn Error>> {
+    let mut board = CircuitBoard::from_instructions(input)?;
+    let mut cache = board.new_cache();
+
+    let original_a = board
+        .get_signal_by_name("a", &mut cache)
+        .ok_or("no signal reaches wire `a`")?;
+    let b = board.wires.get("b").ok_or("there is no wire `b`")?;
+    board.components[b.index()] =
+        Some(CircuitComponent::Direct(ComponentInput::Signal(original_a)));
+
+    cache.fill(None);
+    Ok(board
+        .get_signal_by_name("a", &mut cache)
+        .ok_or("no signal reaches wire `a`")?)
 }
 
 #[cfg(test)]
@@ -174,10 +217,13 @@ mod tests {
     #[case("y", Some(456))]
     fn test_from_instructions(#[case] wire_id: String, #[case] expected_signal: Option<u16>) {
         let instructions = "123 -> x\n456 -> y\nx AND y -> d\nx OR y -> e\nx LSHIFT 2 -> f\ny RSHIFT 2 -> g\nNOT x -> h\nNOT y -> i";
-        let board = CircuitBoard::from_instructions(instructions);
+        let board = CircuitBoard::from_instructions(instructions).unwrap();
 
-        let mut cache = ResCache::new();
+        let mut cache = board.new_cache();
 
-        assert_eq!(board.get_signal(&wire_id, &mut cache), expected_signal);
+        assert_eq!(
+            board.get_signal_by_name(&wire_id, &mut cache),
+            expected_signal
+        );
     }
 }
@@ -6,10 +6,7 @@ pub fn part_one(input: &str) -> Option<i32> {
     Some(
         input
             .split(['[', ']', '{', '}', ':', ','])
-            .filter_map(|val| match val.parse::<i32>() {
-                Ok(num) => Some(num),
-                Err(_) => None,
-            })
+            .filter_map(|val| val.parse::<i32>().ok())
             .sum(),
     )
 }
@@ -1,93 +1,69 @@
-use std::collections::{HashMap, HashSet};
+use std::error::Error;
 
-use itertools::Itertools;
+use advent_of_code::{
+    intern::{Interner, Symbol},
+    parse::{self, ParseError},
+    parse_line,
+    route::DistanceMatrix,
+};
 
 advent_of_code::solution!(13);
 
-struct HappinessMatrix {
-    neighbor_happiness: HashMap<(String, String), i32>,
-}
-
-impl HappinessMatrix {
-    fn from_input(input: &str) -> Self {
-        Self {
-            neighbor_happiness: input.lines().fold(HashMap::new(), |mut map, line| {
-                let (first_part, next_to_part) = line
-                    .split_once(" happiness units by sitting next to ")
-                    .unwrap();
-
-                let (who, change_part) = first_part.split_once(" would ").unwrap();
-
-                let (change, by_part) = change_part.split_once(" ").unwrap();
-
-                let mut by = by_part.parse::<i32>().unwrap();
-                if change == "lose" {
-                    by *= -1;
-                }
-
-                let next_to = next_to_part.strip_suffix(".").unwrap();
-
-                map.insert((who.to_string(), next_to.to_string()), by);
-
-                map
-            }),
+/// Happiness of each pair of neighbours, as the sum of what both of them gain or lose by sitting next to each other.
+/// With `include_myself`, adds a guest that everyone is indifferent about.
+fn happiness_matrix(input: &str, include_myself: bool) -> Result<DistanceMatrix<i32>, ParseError> {
+    let mut names = Interner::new();
+    let rules = parse::lines(input, |line| {
+        if line.contains(" would gain ") {
+            parse_line!(
+                "{} would gain {} happiness units by sitting next to {}.",
+                line, &mut names => Symbol, i32, Symbol
+            )
+        } else if line.contains(" would lose ") {
+            let (who, by, next_to) = parse_line!(
+                "{} would lose {} happiness units by sitting next to {}.",
+                line, &mut names => Symbol, i32, Symbol
+            )?;
+            Ok((who, -by, next_to))
+        } else {
+            let change = match line.split_once(" would ") {
+                Some((_, rest)) => rest.split(' ').next().unwrap_or(rest),
+                None => line,
+            };
+            Err(ParseError::at(line, change, "`gain` or `lose`"))
+        }
+    })?;
+    let guests: Vec<Symbol> = names.symbols().collect();
+    let myself = include_myself.then(|| names.intern("~~~ME~~~"));
+
+    let mut happiness = DistanceMatrix::with_places(names);
+    if let Some(myself) = myself {
+        for guest in guests {
+            happiness.set_symmetric(myself, guest, 0);
         }
     }
-
-    fn add_myself(&mut self) {
-        self.all_guests().into_iter().for_each(|guest| {
-            self.neighbor_happiness
-                .insert(("~~~ME~~~".to_string(), guest.clone()), 0);
-            self.neighbor_happiness
-                .insert((guest.clone(), "~~~ME~~~".to_string()), 0);
-        });
-    }
-
-    fn all_guests(&self) -> HashSet<String> {
-        self.neighbor_happiness
-            .keys()
-            .fold(HashSet::new(), |mut set, (guest, _)| {
-                set.insert(guest.clone());
-                set
-            })
-    }
-
-    fn evaluate_total_happiness(&self, arrangement: Vec<String>) -> i32 {
-        let len = arrangement.len();
-
-        (0..len)
-            .map(|index| {
-                let me = arrangement[index].clone();
-                let left_neighbor =
-                    arrangement[(index as i32 - 1).rem_euclid(len as i32) as usize].clone();
-                let right_neighbor = arrangement[(index + 1).rem_euclid(len)].clone();
-
-                self.neighbor_happiness[&(me.clone(), left_neighbor)]
-                    + self.neighbor_happiness[&(me.clone(), right_neighbor)]
-            })
-            .sum()
+    for (who, by, next_to) in rules {
+        let total = happiness
+            .get(who.index(), next_to.index())
+            .unwrap_or_default()
+            + by;
+        happiness.set_symmetric(who, next_to, total);
     }
+    Ok(happiness)
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let happiness_matrix = HappinessMatrix::from_input(input);
-    happiness_matrix
-        .all_guests()
-        .into_iter()
-        .permutations(happiness_matrix.all_guests().len())
-        .map(|p| happiness_matrix.evaluate_total_happiness(p))
-        .max()
+pub fn part_one(input: &str) -> Result<i32, Box<dyn Error>> {
+    let route = happiness_matrix(input, false)?.longest_cycle();
+    Ok(route
+        .ok_or("no seating puts everyone between two neighbours")?
+        .value)
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let mut happiness_matrix = HappinessMatrix::from_input(input);
-    happiness_matrix.add_myself();
-    happiness_matrix
-        .all_guests()
-        .into_iter()
-        .permutations(happiness_matrix.all_guests().len())
-        .map(|p| happiness_matrix.evaluate_total_happiness(p))
-        .max()
+pub fn part_two(input: &str) -> Result<i32, Box<dyn Error>> {
+    let route = happiness_matrix(input, true)?.longest_cycle();
+    Ok(route
+        .ok_or("no seating puts everyone between two neighbours")?
+        .value)
 }
 
 #[cfg(test)]
@@ -97,12 +73,23 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(330));
+        assert_eq!(result.unwrap(), 330);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert!(result.is_some());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_rejects_unknown_changes() {
+        let error = happiness_matrix(
+            "Alice would win 54 happiness units by sitting next to Bob.",
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(error.found(), "win");
+        assert_eq!(error.line_number, Some(1));
     }
 }
@@ -0,0 +1,412 @@
+/// Subsets with a given sum, compositions of a number into parts, and partitions into groups with equal sums.
+/// Iterators enumerate lazily, `count_*` functions count with dynamic programming instead of enumerating.
+use std::iter::FusedIterator;
+
+/// Lazily enumerates the subsets of `items` whose values sum to `target`, as increasing indices into `items`.
+/// Branches that can no longer reach the target are pruned. Created by [`subsets_with_sum`].
+#[derive(Clone, Debug)]
+pub struct SubsetsWithSum<'a> {
+    items: &'a [u64],
+    target: u64,
+    max_len: usize,
+    /// `suffix[i]` is the sum of `items[i..]`.
+    suffix: Vec<u64>,
+    chosen: Vec<usize>,
+    sum: u64,
+    /// Next index to decide on.
+    cursor: usize,
+    is_empty_pending: bool,
+    is_done: bool,
+}
+
+/// Subsets of `items` whose values sum to `target`, see [`SubsetsWithSum`].
+pub fn subsets_with_sum(items: &[u64], target: u64) -> SubsetsWithSum<'_> {
+    let mut suffix = vec![0; items.len() + 1];
+    for i in (0..items.len()).rev() {
+        suffix[i] = suffix[i + 1] + items[i];
+    }
+
+    SubsetsWithSum {
+        items,
+        target,
+        max_len: items.len(),
+        suffix,
+        chosen: vec![],
+        sum: 0,
+        cursor: 0,
+        is_empty_pending: target == 0,
+        is_done: false,
+    }
+}
+
+impl SubsetsWithSum<'_> {
+    /// Only enumerates subsets of at most `max_len` items, pruning larger ones early.
+    #[must_use]
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+impl Iterator for SubsetsWithSum<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_empty_pending {
+            self.is_empty_pending = false;
+            return Some(vec![]);
+        }
+
+        while !self.is_done {
+            let can_extend = self.cursor < self.items.len()
+                && self.chosen.len() < self.max_len
+                && self.sum + self.suffix[self.cursor] >= self.target;
+
+            if can_extend {
+                let i = self.cursor;
+                self.cursor += 1;
+                if self.sum + self.items[i] <= self.target {
+                    self.chosen.push(i);
+                    self.sum += self.items[i];
+                    if self.sum == self.target {
+                        return Some(self.chosen.clone());
+                    }
+                }
+                continue;
+            }
+
+            // backtrack: leave out the last chosen item and try the ones after it.
+            match self.chosen.pop() {
+                Some(last) => {
+                    self.sum -= self.items[last];
+                    self.cursor = last + 1;
+                }
+                None => self.is_done = true,
+            }
+        }
+
+        None
+    }
+}
+
+impl FusedIterator for SubsetsWithSum<'_> {}
+
+/// Number of subsets of `items` whose values sum to `target`.
+pub fn count_subsets_with_sum(items: &[u64], target: u64) -> u64 {
+    let target = usize::try_from(target).expect("target too large");
+    let mut ways = vec![0_u64; target + 1];
+    ways[0] = 1;
+
+    for item in items
+        .iter()
+        .map(|i| usize::try_from(*i).unwrap_or(usize::MAX))
+    {
+        for sum in (item..=target).rev() {
+            ways[sum] += ways[sum - item];
+        }
+    }
+    ways[target]
+}
+
+/// Number of subsets of `items` whose values sum to `target`, by size: the result at index `k` counts subsets of `k` items.
+pub fn count_subsets_with_sum_by_len(items: &[u64], target: u64) -> Vec<u64> {
+    let target = usize::try_from(target).expect("target too large");
+    // ways[k][sum]
+    let mut ways = vec![vec![0_u64; target + 1]; items.len() + 1];
+    ways[0][0] = 1;
+
+    for (i, item) in items
+        .iter()
+        .map(|i| usize::try_from(*i).unwrap_or(usize::MAX))
+        .enumerate()
+    {
+        for len in (1..=i + 1).rev() {
+            for sum in (item..=target).rev() {
+                ways[len][sum] += ways[len - 1][sum - item];
+            }
+        }
+    }
+    ways.into_iter().map(|by_sum| by_sum[target]).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Lazily enumerates the ways to write `n` as an ordered sum of `k` parts within `min..=max`, in lexicographic order.
+/// Created by [`compositions`] and [`bounded_compositions`].
+#[derive(Clone, Debug)]
+pub struct Compositions {
+    n: u64,
+    k: usize,
+    min: u64,
+    max: u64,
+    parts: Option<Vec<u64>>,
+    is_started: bool,
+}
+
+/// Compositions of `n` into `k` parts, each at least `0`, see [`Compositions`].
+pub fn compositions(n: u64, k: usize) -> Compositions {
+    bounded_compositions(n, k, 0, n)
+}
+
+/// Compositions of `n` into `k` parts, each within `min..=max`, see [`Compositions`].
+pub fn bounded_compositions(n: u64, k: usize, min: u64, max: u64) -> Compositions {
+    Compositions {
+        n,
+        k,
+        min,
+        max,
+        parts: None,
+        is_started: false,
+    }
+}
+
+impl Compositions {
+    /// Smallest and largest value of a part, if `remaining` is split into `count` parts starting with it.
+    fn range(&self, remaining: u64, count: usize) -> Option<(u64, u64)> {
+        let others = count as u64 - 1;
+        let low = self
+            .min
+            .max(remaining.saturating_sub(others.saturating_mul(self.max)));
+        let high = self
+            .max
+            .min(remaining.checked_sub(others.checked_mul(self.min)?)?);
+        (low <= high).then_some((low, high))
+    }
+
+    /// Fills `parts[from..]` with the smallest values that split `remaining`.
+    fn fill(&self, parts: &mut [u64], from: usize, mut remaining: u64) -> Option<()> {
+        for (i, part) in parts.iter_mut().enumerate().skip(from) {
+            let (low, _) = self.range(remaining, self.k - i)?;
+            *part = low;
+            remaining -= low;
+        }
+        Some(())
+    }
+}
+
+impl Iterator for Compositions {
+    type Item = Vec<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.is_started {
+            self.is_started = true;
+            if self.k == 0 {
+                return (self.n == 0).then(Vec::new);
+            }
+            let mut parts = vec![0; self.k];
+            self.fill(&mut parts, 0, self.n)?;
+            self.parts = Some(parts.clone());
+            return Some(parts);
+        }
+
+        let mut parts = self.parts.take()?;
+
+        // increment the rightmost part that can grow, the last part is determined by the others.
+        let mut remaining: Vec<u64> = Vec::with_capacity(self.k);
+        let mut left = self.n;
+        for part in &parts {
+            remaining.push(left);
+            left -= part;
+        }
+
+        for i in (0..self.k - 1).rev() {
+            let (_, high) = self.range(remaining[i], self.k - i)?;
+            if parts[i] < high {
+                parts[i] += 1;
+                let rest = remaining[i] - parts[i];
+                self.fill(&mut parts, i + 1, rest)?;
+                self.parts = Some(parts.clone());
+                return Some(parts);
+            }
+        }
+
+        None
+    }
+}
+
+impl FusedIterator for Compositions {}
+
+/// Number of compositions of `n` into `k` parts within `min..=max`.
+pub fn count_bounded_compositions(n: u64, k: usize, min: u64, max: u64) -> u64 {
+    let n = usize::try_from(n).expect("n too large");
+    let (min, max) = (min as usize, (max as usize).min(n));
+
+    // ways[sum] after adding each part.
+    let mut ways = vec![0_u64; n + 1];
+    ways[0] = 1;
+    for _ in 0..k {
+        let mut next = vec![0_u64; n + 1];
+        for (sum, count) in ways.iter().enumerate().filter(|(_, c)| **c > 0) {
+            for part in min..=max.min(n - sum) {
+                next[sum + part] += count;
+            }
+        }
+        ways = next;
+    }
+    ways[n]
+}
+
+/// Number of compositions of `n` into `k` parts, each at least `0`.
+pub fn count_compositions(n: u64, k: usize) -> u64 {
+    count_bounded_compositions(n, k, 0, n)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Splits `items` into `k` groups with equal sums, as indices into `items`.
+/// The first group has as few items as possible, ties are broken by the smallest `key` of the first group.
+/// The other groups are any valid split of the remaining items. Returns `None` if no split exists.
+///
+/// ```ignore
+/// // the group with the fewest packages and the smallest product goes first.
+/// let groups = partition_min_by_key(&weights, 3, |group| group.iter().map(|i| weights[*i]).product::<u64>());
+/// ```
+pub fn partition_min_by_key<K: Ord>(
+    items: &[u64],
+    k: usize,
+    key: impl Fn(&[usize]) -> K,
+) -> Option<Vec<Vec<usize>>> {
+    let total: u64 = items.iter().sum();
+    if k == 0 || !total.is_multiple_of(k as u64) {
+        return None;
+    }
+    let target = total / k as u64;
+
+    for len in 0..=items.len() {
+        let mut candidates: Vec<Vec<usize>> = subsets_with_sum(items, target)
+            .max_len(len)
+            .filter(|group| group.len() == len)
+            .collect();
+        candidates.sort_by_cached_key(|group| key(group));
+
+        for first in candidates {
+            let rest: Vec<usize> = (0..items.len()).filter(|i| !first.contains(i)).collect();
+            if let Some(mut groups) = split_equal(items, &rest, k - 1, target) {
+                groups.insert(0, first);
+                return Some(groups);
+            }
+        }
+    }
+    None
+}
+
+/// Splits the items at `indices` into `k` groups that each sum to `target`.
+fn split_equal(items: &[u64], indices: &[usize], k: usize, target: u64) -> Option<Vec<Vec<usize>>> {
+    if k <= 1 {
+        return Some(vec![indices.to_vec(); k]);
+    }
+
+    let values: Vec<u64> = indices.iter().map(|i| items[*i]).collect();
+    subsets_with_sum(&values, target).find_map(|group| {
+        let group: Vec<usize> = group.into_iter().map(|i| indices[i]).collect();
+        let rest: Vec<usize> = indices
+            .iter()
+            .copied()
+            .filter(|i| !group.contains(i))
+            .collect();
+        let mut groups = split_equal(items, &rest, k - 1, target)?;
+        groups.insert(0, group);
+        Some(groups)
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        bounded_compositions, compositions, count_bounded_compositions, count_compositions,
+        count_subsets_with_sum, count_subsets_with_sum_by_len, partition_min_by_key,
+        subsets_with_sum,
+    };
+
+    const CONTAINERS: [u64; 5] = [20, 15, 10, 5, 5];
+
+    #[test]
+    fn enumerates_subsets_with_sum() {
+        let subsets: Vec<Vec<usize>> = subsets_with_sum(&CONTAINERS, 25).collect();
+        assert_eq!(
+            subsets,
+            vec![vec![0, 3], vec![0, 4], vec![1, 2], vec![1, 3, 4]]
+        );
+        assert_eq!(subsets_with_sum(&CONTAINERS, 25).max_len(2).count(), 3);
+        assert_eq!(
+            subsets_with_sum(&CONTAINERS, 0).collect::<Vec<_>>(),
+            vec![Vec::<usize>::new()]
+        );
+        assert_eq!(subsets_with_sum(&CONTAINERS, 100).count(), 0);
+        assert_eq!(subsets_with_sum(&[0, 1], 1).count(), 2);
+    }
+
+    #[test]
+    fn counts_subsets_with_sum() {
+        assert_eq!(count_subsets_with_sum(&CONTAINERS, 25), 4);
+        assert_eq!(
+            count_subsets_with_sum_by_len(&CONTAINERS, 25),
+            vec![0, 0, 3, 1, 0, 0]
+        );
+        assert_eq!(count_subsets_with_sum(&CONTAINERS, 0), 1);
+    }
+
+    #[test]
+    fn enumerates_compositions() {
+        let all: Vec<Vec<u64>> = compositions(2, 3).collect();
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 0, 2],
+                vec![0, 1, 1],
+                vec![0, 2, 0],
+                vec![1, 0, 1],
+                vec![1, 1, 0],
+                vec![2, 0, 0]
+            ]
+        );
+
+        let bounded: Vec<Vec<u64>> = bounded_compositions(7, 3, 2, 3).collect();
+        assert_eq!(bounded, vec![vec![2, 2, 3], vec![2, 3, 2], vec![3, 2, 2]]);
+
+        assert_eq!(bounded_compositions(10, 2, 0, 4).count(), 0);
+        assert_eq!(compositions(0, 0).count(), 1);
+        assert_eq!(compositions(3, 1).collect::<Vec<_>>(), vec![vec![3]]);
+
+        // parts without an upper bound, and lower bounds that cannot fit.
+        assert_eq!(
+            bounded_compositions(5, 3, 0, u64::MAX).collect::<Vec<_>>(),
+            compositions(5, 3).collect::<Vec<_>>()
+        );
+        assert_eq!(bounded_compositions(5, 3, 0, u64::MAX).count(), 21);
+        assert_eq!(
+            bounded_compositions(5, 3, u64::MAX / 2, u64::MAX).count(),
+            0
+        );
+        assert_eq!(count_bounded_compositions(5, 3, 0, u64::MAX), 21);
+    }
+
+    #[test]
+    fn counts_compositions() {
+        assert_eq!(count_compositions(100, 4), 176_851);
+        assert_eq!(
+            count_compositions(100, 4),
+            compositions(100, 4).count() as u64
+        );
+        assert_eq!(count_bounded_compositions(7, 3, 2, 3), 3);
+        assert_eq!(count_bounded_compositions(10, 2, 0, 4), 0);
+    }
+
+    #[test]
+    fn partitions_with_smallest_first_group() {
+        let weights = [1, 2, 3, 4, 5, 7, 8, 9, 10, 11];
+        let product = |group: &[usize]| group.iter().map(|i| weights[*i]).product::<u64>();
+
+        let groups = partition_min_by_key(&weights, 3, product).unwrap();
+        assert_eq!(product(&groups[0]), 99);
+        assert_eq!(groups.len(), 3);
+        assert!(groups
+            .iter()
+            .all(|group| group.iter().map(|i| weights[*i]).sum::<u64>() == 20));
+
+        let groups = partition_min_by_key(&weights, 4, product).unwrap();
+        assert_eq!(product(&groups[0]), 44);
+
+        assert_eq!(partition_min_by_key(&[1, 2], 2, product), None);
+    }
+}
@@ -0,0 +1,445 @@
+/// A heap-backed, rectangular 2D grid.
+/// Positions are `(x, y)` tuples, with `x` counting columns from the left and `y` counting rows from the top.
+use std::{
+    fmt::Display,
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
+    str::FromStr,
+};
+
+use crate::point::{Direction, Point2};
+
+/// Offsets of the 4 orthogonal neighbours, clockwise from the top.
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the 8 orthogonal and diagonal neighbours, clockwise from the top.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Error returned when a character map is not rectangular.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseGridError {
+    /// Row (counting from 0) with an unexpected width.
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {} is {} characters wide, expected {}.",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to the result of `f(x, y)`.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a character map, converting each character with `f`. Trailing line breaks are ignored.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let found = cells.len() - before;
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(ParseGridError {
+                        row,
+                        expected,
+                        found,
+                    })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if `(x, y)` lies within the grid.
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn index_of(&self, position: (usize, usize)) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.1 * self.width + position.0)
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves `position` by `offset`, if the result lies within the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let moved = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(moved).then_some(moved)
+    }
+
+    /// Moves `position` one step in `direction`, if the result lies within the grid.
+    pub fn step(&self, position: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.offset(position, direction.offset())
+    }
+
+    /// Positions of the orthogonal neighbours of `position` that lie within the grid, clockwise from the top.
+    pub fn neighbours4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours of `position` that lie within the grid, clockwise from the top.
+    pub fn neighbours8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Cells of row `y`, from left to right.
+    ///
+    /// # Panics
+    /// If `y` lies outside of the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Mutable cells of row `y`, from left to right.
+    ///
+    /// # Panics
+    /// If `y` lies outside of the grid.
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Cells of column `x`, from top to bottom.
+    ///
+    /// # Panics
+    /// If `x` lies outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Mutable cells of column `x`, from top to bottom.
+    ///
+    /// # Panics
+    /// If `x` lies outside of the grid.
+    pub fn column_mut(&mut self, x: usize) -> impl Iterator<Item = &mut T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter_mut().skip(x).step_by(self.width)
+    }
+
+    /// Rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of 0, an empty row has no cells either way.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// All mutable cells with their positions, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// All cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Position of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// Cells with their positions in the rectangle spanned by `xs` and `ys`, row by row.
+    /// The rectangle is clipped to the grid.
+    pub fn region(
+        &self,
+        xs: impl RangeBounds<usize>,
+        ys: impl RangeBounds<usize>,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let (xs, ys) = (clip(xs, self.width), clip(ys, self.height));
+        self.rows()
+            .enumerate()
+            .take(ys.end)
+            .skip(ys.start)
+            .flat_map(move |(y, row)| {
+                let start = xs.start;
+                row[xs.clone()]
+                    .iter()
+                    .enumerate()
+                    .map(move |(x, cell)| ((start + x, y), cell))
+            })
+    }
+
+    /// Mutable cells with their positions in the rectangle spanned by `xs` and `ys`, row by row.
+    /// The rectangle is clipped to the grid.
+    pub fn region_mut(
+        &mut self,
+        xs: impl RangeBounds<usize>,
+        ys: impl RangeBounds<usize>,
+    ) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let (xs, ys) = (clip(xs, self.width), clip(ys, self.height));
+        self.cells
+            .chunks_mut(self.width.max(1))
+            .enumerate()
+            .take(ys.end)
+            .skip(ys.start)
+            .flat_map(move |(y, row)| {
+                let start = xs.start;
+                row[xs.clone()]
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(x, cell)| ((start + x, y), cell))
+            })
+    }
+
+    /// Creates a grid of the same size by converting each cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+/// Converts `range` to a half-open range within `0..len`.
+fn clip(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    let end = end.min(len);
+    start.min(end)..end
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get(position).unwrap_or_else(|| {
+            panic!("position {position:?} is out of bounds of a {width}x{height} grid")
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!("position {position:?} is out of bounds of a {width}x{height} grid")
+        })
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point2<usize>) -> &Self::Output {
+        &self[(position.x, position.y)]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: Point2<usize>) -> &mut Self::Output {
+        &mut self[(position.x, position.y)]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, |c| c)
+    }
+}
+
+/// Renders the grid row by row, each row on its own line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+    use crate::point::{Direction, Point2};
+
+    fn get_mock_grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = get_mock_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'b');
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let digits = Grid::parse("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.cells(), &[1, 2, 3, 4]);
+
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(ParseGridError {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn iterates_neighbours_within_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours8((2, 0)).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.step((1, 0), Direction::Down), Some((1, 1)));
+        assert_eq!(grid.step((1, 0), Direction::Up), None);
+        assert_eq!(grid[Point2::new(1, 2)], 0);
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let mut grid = get_mock_grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+
+        grid.column_mut(0).for_each(|c| *c = '.');
+        grid.row_mut(1)[2] = '#';
+        assert_eq!(grid.to_string(), ".bc\n.e#\n");
+        assert_eq!(grid.position(|c| *c == '#'), Some((2, 1)));
+    }
+
+    #[test]
+    fn iterates_and_mutates_regions() {
+        let mut grid = Grid::from_fn(4, 4, |x, y| x + 10 * y);
+        let region: Vec<_> = grid.region(1..=2, 2..).map(|(p, v)| (p, *v)).collect();
+        assert_eq!(
+            region,
+            vec![((1, 2), 21), ((2, 2), 22), ((1, 3), 31), ((2, 3), 32)]
+        );
+
+        grid.region_mut(2.., ..1).for_each(|(_, v)| *v = 0);
+        assert_eq!(grid.row(0), &[0, 1, 0, 0]);
+
+        // regions are clipped to the grid.
+        assert_eq!(grid.region(3..10, 3..10).count(), 1);
+        assert_eq!(grid.region(5..10, ..).count(), 0);
+    }
+}
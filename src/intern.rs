@@ -0,0 +1,109 @@
+/// Interning of names into dense ids, so name-keyed puzzles can store their data in `Vec`s and matrices.
+use std::{collections::HashMap, fmt::Display, ops::Index};
+
+/// Dense id of an interned name. Ids are assigned in order of first appearance, starting at `0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(pub u32);
+
+impl Symbol {
+    /// The id as an index into a `Vec` or matrix.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Two-way map between names and [`Symbol`]s.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Interner {
+    names: Vec<String>,
+    ids: HashMap<String, Symbol>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `name`, assigning the next free id if it is new.
+    ///
+    /// # Panics
+    /// If more than `u32::MAX` names are interned.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.ids.get(name) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(u32::try_from(self.names.len()).expect("too many interned names"));
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), symbol);
+        symbol
+    }
+
+    /// Id of `name`, if it was interned.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.ids.get(name).copied()
+    }
+
+    /// Name of `symbol`.
+    ///
+    /// # Panics
+    /// If `symbol` was not created by this interner.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.index()]
+    }
+
+    /// Number of interned names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// All ids with their names, in order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        (0..).map(Symbol).zip(self.names.iter().map(String::as_str))
+    }
+
+    /// All ids, in order.
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol> {
+        (0..self.names.len() as u32).map(Symbol)
+    }
+}
+
+impl Index<Symbol> for Interner {
+    type Output = str;
+
+    fn index(&self, symbol: Symbol) -> &Self::Output {
+        self.resolve(symbol)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interner, Symbol};
+
+    #[test]
+    fn interns_names() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("London"), Symbol(0));
+        assert_eq!(interner.intern("Dublin"), Symbol(1));
+        assert_eq!(interner.intern("London"), Symbol(0));
+        assert_eq!(interner.len(), 2);
+
+        assert_eq!(interner.get("Dublin"), Some(Symbol(1)));
+        assert_eq!(interner.get("Belfast"), None);
+        assert_eq!(&interner[Symbol(1)], "Dublin");
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            vec![(Symbol(0), "London"), (Symbol(1), "Dublin")]
+        );
+    }
+}
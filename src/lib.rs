@@ -1,3 +1,12 @@
+pub mod combinatorics;
+pub mod grid;
+pub mod intern;
+pub mod number_theory;
+pub mod parallel;
+pub mod parse;
+pub mod point;
+pub mod route;
+pub mod search;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
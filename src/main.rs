@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, doctor, download, export, read, scaffold, scale, solve, stars, time, vault,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,8 +9,11 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::{export::ReportFormat, time::MergePolicy, vault::VaultCommand},
+        Day,
+    };
+    use std::{path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
@@ -25,15 +30,34 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            alloc: bool,
             submit: Option<u8>,
+            watch: bool,
         },
         All {
             release: bool,
+            alloc: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            merge_policy: MergePolicy,
+            table: Option<String>,
+            alloc: bool,
+            isolate: bool,
+        },
+        Scale {
+            day: Day,
+        },
+        Doctor,
+        Stars,
+        Export {
+            format: ReportFormat,
+            output: Option<PathBuf>,
+        },
+        Vault {
+            command: VaultCommand,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,15 +69,24 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                alloc: args.contains("--alloc"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let merge_policy = args.opt_value_from_str("--merge")?.unwrap_or_default();
+                let table = args.opt_value_from_str("--table")?;
+                let alloc = args.contains("--alloc");
+                let isolate = args.contains("--isolate");
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    merge_policy,
+                    table,
+                    alloc,
+                    isolate,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -71,6 +104,22 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                alloc: args.contains("--alloc"),
+                watch: args.contains("--watch"),
+            },
+            Some("scale") => AppArguments::Scale {
+                day: args.free_from_str()?,
+            },
+            Some("doctor") => AppArguments::Doctor,
+            Some("stars") => AppArguments::Stars,
+            Some("export") => AppArguments::Export {
+                format: args
+                    .opt_value_from_str("--format")?
+                    .unwrap_or(ReportFormat::Markdown),
+                output: args.opt_value_from_str("--output")?,
+            },
+            Some("vault") => AppArguments::Vault {
+                command: args.free_from_str()?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -100,8 +149,16 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All { release, alloc } => all::handle(release, alloc),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                merge_policy,
+                table,
+                alloc,
+                isolate,
+            } => time::handle(day, all, store, merge_policy, table, alloc, isolate),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day, download } => {
@@ -114,8 +171,15 @@ fn main() {
                 day,
                 release,
                 dhat,
+                alloc,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+            } => solve::handle(day, release, dhat, alloc, submit, watch),
+            AppArguments::Scale { day } => scale::handle(day),
+            AppArguments::Doctor => doctor::handle(),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Export { format, output } => export::handle(format, output),
+            AppArguments::Vault { command } => vault::handle(&command),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -0,0 +1,234 @@
+/// Divisor sums, modular arithmetic and the positions of numbers that are filled in along diagonals.
+use std::mem;
+
+/// Sum of the divisors of every number up to `limit`: the result at index `n` is `σ(n)`, with `σ(0) = 0`.
+pub fn divisor_sums(limit: usize) -> Vec<u64> {
+    divisor_sums_limited(limit, usize::MAX)
+}
+
+/// Like [`divisor_sums`], but each divisor `d` only counts for its first `max_multiples` multiples `d, 2d, …`.
+/// E.g. elves that stop after delivering to 50 houses.
+pub fn divisor_sums_limited(limit: usize, max_multiples: usize) -> Vec<u64> {
+    let mut sums = vec![0; limit + 1];
+    for divisor in 1..=limit {
+        for multiple in (divisor..=limit).step_by(divisor).take(max_multiples) {
+            sums[multiple] += divisor as u64;
+        }
+    }
+    sums
+}
+
+/// Greatest common divisor, with `gcd(0, 0) = 0`.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        a %= b;
+        mem::swap(&mut a, &mut b);
+    }
+    a
+}
+
+/// Least common multiple, with `lcm(0, n) = 0`.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+/// Extended Euclidean algorithm: returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `base^exp mod modulus`, by repeated squaring.
+///
+/// # Panics
+/// If `modulus` is `0`.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert_ne!(modulus, 0, "modulus must not be 0");
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Inverse of `a` modulo `modulus`, if `a` and `modulus` are coprime.
+///
+/// # Panics
+/// If `modulus` is `0`.
+pub fn mod_inv(a: i64, modulus: i64) -> Option<i64> {
+    assert_ne!(modulus, 0, "modulus must not be 0");
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Chinese remainder theorem: the smallest non-negative `x` with `x ≡ residue (mod modulus)` for all
+/// `(residue, modulus)` pairs, together with the combined modulus. Moduli do not need to be coprime.
+/// Returns `None` if the congruences contradict each other.
+///
+/// # Panics
+/// If any modulus is `0`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0_i64, 1_i64), |(x, m), &(residue, modulus)| {
+            assert_ne!(modulus, 0, "modulus must not be 0");
+            let residue = residue.rem_euclid(modulus);
+            let (g, p, _) = extended_gcd(m, modulus);
+            let difference = residue - x;
+            if difference % g != 0 {
+                return None;
+            }
+
+            let step = modulus / g;
+            let combined = m.checked_mul(step)?;
+            let k = i128::from(difference / g) * i128::from(p) % i128::from(step);
+            let x = i128::from(x) + i128::from(m) * k;
+            Some((
+                i64::try_from(x.rem_euclid(i128::from(combined))).ok()?,
+                combined,
+            ))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// `n`-th triangular number, `1 + 2 + … + n`.
+pub fn triangular(n: u64) -> u64 {
+    n * (n + 1) / 2
+}
+
+/// Index of `(row, column)` when a grid is filled along diagonals from the bottom left to the top right,
+/// like `1` at `(1, 1)`, `2` at `(2, 1)`, `3` at `(1, 2)`, `4` at `(3, 1)`. Rows, columns and indices count from `1`.
+///
+/// # Panics
+/// If `row` or `column` is `0`.
+pub fn diagonal_index(row: u64, column: u64) -> u64 {
+    assert!(row >= 1 && column >= 1, "rows and columns count from 1");
+    triangular(row + column - 2) + column
+}
+
+/// Inverse of [`diagonal_index`]: the `(row, column)` of an index, counting from `1`.
+///
+/// # Panics
+/// If `index` is `0`.
+pub fn diagonal_position(index: u64) -> (u64, u64) {
+    assert_ne!(index, 0, "indices count from 1");
+    // largest diagonal `d` with `triangular(d) < index`.
+    let mut diagonal = ((((8 * index) as f64).sqrt() - 1.0) / 2.0) as u64;
+    while triangular(diagonal) >= index {
+        diagonal -= 1;
+    }
+    while triangular(diagonal + 1) < index {
+        diagonal += 1;
+    }
+
+    let column = index - triangular(diagonal);
+    (diagonal + 2 - column, column)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        crt, diagonal_index, diagonal_position, divisor_sums, divisor_sums_limited, extended_gcd,
+        gcd, lcm, mod_inv, mod_pow, triangular,
+    };
+
+    #[test]
+    fn sums_divisors() {
+        assert_eq!(divisor_sums(9), vec![0, 1, 3, 4, 7, 6, 12, 8, 15, 13]);
+        // 1 only reaches houses 1 and 2, 2 reaches 2 and 4, 3 reaches 3 and 6.
+        assert_eq!(divisor_sums_limited(6, 2), vec![0, 1, 3, 3, 6, 5, 9]);
+    }
+
+    #[test]
+    fn computes_divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(2, 4), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_zero_moduli() {
+        mod_inv(3, 0);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_zero_moduli_in_congruences() {
+        crt(&[(2, 3), (1, 0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_zero_coordinates() {
+        diagonal_index(0, 3);
+    }
+
+    #[test]
+    fn indexes_diagonals() {
+        assert_eq!(triangular(4), 10);
+        assert_eq!(diagonal_index(1, 1), 1);
+        assert_eq!(diagonal_index(4, 2), 12);
+        assert_eq!(diagonal_index(1, 6), 21);
+        for index in 1..100 {
+            let (row, column) = diagonal_position(index);
+            assert_eq!(diagonal_index(row, column), index);
+        }
+
+        // the code grid: each code is the previous one times 252533, modulo 33554393.
+        let code = |row, column| {
+            20151125 * mod_pow(252533, diagonal_index(row, column) - 1, 33554393) % 33554393
+        };
+        assert_eq!(code(2, 1), 31916031);
+        assert_eq!(code(6, 6), 27995004);
+    }
+}
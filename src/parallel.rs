@@ -0,0 +1,125 @@
+/// Searches that spread a range of candidates over all cores.
+use std::{
+    num::NonZeroUsize,
+    ops::{Bound, RangeBounds},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering::Relaxed},
+    thread,
+};
+
+/// Smallest number in `range` that satisfies `predicate`, checked on all cores.
+///
+/// Threads take chunks of `chunk` numbers in increasing order and scan each chunk in order.
+/// Once a match is found, chunks that start after it are no longer taken, so the result is the same
+/// as `range.find(predicate)`. An unbounded range ends at `u64::MAX`, which is included.
+pub fn par_find_first(
+    range: impl RangeBounds<u64>,
+    chunk: u64,
+    predicate: impl Fn(u64) -> bool + Sync,
+) -> Option<u64> {
+    par_find_first_with(range, chunk, || (), |_, n| predicate(n))
+}
+
+/// Like [`par_find_first`], but each thread creates a state with `init` and passes it to `predicate`,
+/// e.g. to reuse buffers between candidates.
+///
+/// # Panics
+/// If `chunk` is `0`.
+pub fn par_find_first_with<S>(
+    range: impl RangeBounds<u64>,
+    chunk: u64,
+    init: impl Fn() -> S + Sync,
+    predicate: impl Fn(&mut S, u64) -> bool + Sync,
+) -> Option<u64> {
+    assert_ne!(chunk, 0, "chunks must not be empty");
+
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    // inclusive, so ranges can reach `u64::MAX`.
+    let last = match range.end_bound() {
+        Bound::Included(end) => *end,
+        Bound::Excluded(end) => end.checked_sub(1)?,
+        Bound::Unbounded => u64::MAX,
+    };
+    if start > last {
+        return None;
+    }
+
+    // chunks are claimed by index, so claiming never wraps around to numbers before `start`.
+    let next_chunk = AtomicU64::new(0);
+    let found = AtomicU64::new(u64::MAX);
+    let is_found = AtomicBool::new(false);
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let mut state = init();
+                while let Ok(index) =
+                    next_chunk.fetch_update(Relaxed, Relaxed, |i| i.checked_add(1))
+                {
+                    let Some(from) = index
+                        .checked_mul(chunk)
+                        .and_then(|offset| start.checked_add(offset))
+                    else {
+                        break;
+                    };
+                    if from > last || (is_found.load(Relaxed) && from > found.load(Relaxed)) {
+                        break;
+                    }
+
+                    let to = from.saturating_add(chunk - 1).min(last);
+                    if let Some(n) = (from..=to).find(|n| predicate(&mut state, *n)) {
+                        found.fetch_min(n, Relaxed);
+                        is_found.store(true, Relaxed);
+                    }
+                }
+            });
+        }
+    });
+
+    is_found.into_inner().then(|| found.into_inner())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{par_find_first, par_find_first_with};
+
+    #[test]
+    fn finds_the_smallest_match() {
+        assert_eq!(par_find_first(1.., 7, |n| n % 1000 == 999), Some(999));
+        assert_eq!(par_find_first(1000.., 1, |n| n % 1000 == 999), Some(1999));
+        assert_eq!(
+            par_find_first(0..=100, 3, |n| n * n > 50 && n % 2 == 0),
+            Some(8)
+        );
+        assert_eq!(par_find_first(0..100, 16, |n| n > 100), None);
+    }
+
+    #[test]
+    fn stays_within_the_range() {
+        assert_eq!(par_find_first(u64::MAX - 10.., 7, |n| n == 5), None);
+        assert_eq!(
+            par_find_first(u64::MAX - 10.., 7, |n| n == u64::MAX),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            par_find_first(u64::MAX - 3..=u64::MAX, 2, |n| n % 2 == 1),
+            Some(u64::MAX - 2)
+        );
+        assert_eq!(par_find_first(5..5, 1, |_| true), None);
+        assert_eq!(par_find_first(..0, 1, |_| true), None);
+    }
+
+    #[test]
+    fn passes_state_per_thread() {
+        let found = par_find_first_with(0..10_000, 64, String::new, |buffer, n| {
+            buffer.clear();
+            buffer.push_str(&n.to_string());
+            buffer.ends_with("4242")
+        });
+        assert_eq!(found, Some(4242));
+    }
+}
@@ -0,0 +1,407 @@
+/// Declarative parsing of puzzle input lines that follow a fixed pattern, see [`parse_line!`](crate::parse_line).
+use std::{
+    fmt::Display,
+    ops::{Deref, Range},
+    str::FromStr,
+};
+
+use crate::intern::{Interner, Symbol};
+
+/// A single word: non-empty and without whitespace.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Word(pub String);
+
+impl FromStr for Word {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            Err("a single word".into())
+        } else {
+            Ok(Word(s.to_string()))
+        }
+    }
+}
+
+impl Deref for Word {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Error returned when a line of the input does not match what a parser expected.
+/// Renders as an annotated snippet of the offending line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line that was parsed.
+    pub line: String,
+    /// Number of the line in the input, counting from 1. Set with [`ParseError::at_line`].
+    pub line_number: Option<usize>,
+    /// Byte range of the offending part of the line.
+    pub span: Range<usize>,
+    /// Description of what the parser expected.
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: &str, span: Range<usize>, expected: impl Into<String>) -> Self {
+        Self {
+            line: line.into(),
+            line_number: None,
+            span,
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error for `part`, which must be a slice of `line`, e.g. a field returned by `split_once`.
+    /// If it is not, the error spans the whole line.
+    pub fn at(line: &str, part: &str, expected: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        let span = if offset <= line.len() && offset + part.len() <= line.len() {
+            offset..offset + part.len()
+        } else {
+            0..line.len()
+        };
+        Self::new(line, span, expected)
+    }
+
+    /// Sets the number of the line in the input, counting from 1.
+    #[must_use]
+    pub fn at_line(mut self, line_number: usize) -> Self {
+        self.line_number = Some(line_number);
+        self
+    }
+
+    /// The offending part of the line.
+    pub fn found(&self) -> &str {
+        &self.line[self.span.clone()]
+    }
+
+    /// Column of the start of the offending part, counting characters from 1.
+    pub fn column(&self) -> usize {
+        self.line[..self.span.start].chars().count() + 1
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let found = match self.found() {
+            "" => "nothing".to_string(),
+            found => format!("`{found}`"),
+        };
+        let location = match self.line_number {
+            Some(line_number) => format!("line {line_number}, column {}", self.column()),
+            None => format!("column {}", self.column()),
+        };
+        writeln!(f, "expected {}, found {found} at {location}", self.expected)?;
+
+        let gutter = self.line_number.map(|n| n.to_string()).unwrap_or_default();
+        let padding = " ".repeat(gutter.len());
+        let indent = " ".repeat(self.column() - 1);
+        let carets = "^".repeat(self.found().chars().count().max(1));
+
+        writeln!(f, "{padding} |")?;
+        writeln!(f, "{gutter} | {}", self.line)?;
+        write!(f, "{padding} | {indent}{carets}")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line of `input` with `f`, stopping at the first error.
+/// Errors are tagged with the number of the line they occurred on.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+/// Splits `line` at the literal parts of `pattern`, returning the byte range of each `{}` field.
+/// Each field ends at the first occurrence of the literal that follows it, the last one at the end of the line.
+///
+/// # Panics
+/// If `pattern` does not contain `fields` placeholders, or contains two placeholders without a literal in between.
+pub fn split_fields(
+    pattern: &str,
+    line: &str,
+    fields: usize,
+) -> Result<Vec<Range<usize>>, ParseError> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    assert_eq!(
+        literals.len() - 1,
+        fields,
+        "pattern `{pattern}` has {} placeholders, but {fields} types were given",
+        literals.len() - 1
+    );
+    assert!(
+        literals[1..literals.len() - 1]
+            .iter()
+            .all(|l| !l.is_empty()),
+        "pattern `{pattern}` has adjacent placeholders"
+    );
+
+    let (prefix, suffix) = (literals[0], literals[literals.len() - 1]);
+
+    if !line.starts_with(prefix) {
+        let end = line.len().min(prefix.len());
+        let end = (0..=end)
+            .rev()
+            .find(|i| line.is_char_boundary(*i))
+            .unwrap_or(0);
+        return Err(ParseError::new(line, 0..end, format!("`{prefix}`")));
+    }
+    if !line[prefix.len()..].ends_with(suffix) {
+        let start = line.len().saturating_sub(suffix.len()).max(prefix.len());
+        let start = (start..=line.len())
+            .find(|i| line.is_char_boundary(*i))
+            .unwrap_or(line.len());
+        return Err(ParseError::new(
+            line,
+            start..line.len(),
+            format!("`{suffix}` at the end"),
+        ));
+    }
+
+    let end = line.len() - suffix.len();
+    let mut start = prefix.len();
+    let mut spans = Vec::with_capacity(fields);
+
+    for literal in &literals[1..literals.len() - 1] {
+        let Some(offset) = line[start..end].find(literal) else {
+            return Err(ParseError::new(line, start..end, format!("`{literal}`")));
+        };
+        spans.push(start..start + offset);
+        start += offset + literal.len();
+    }
+    spans.push(start..end);
+
+    Ok(spans)
+}
+
+/// Parses a field of `line` as `T`, describing the expected type as `type_name` on failure.
+pub fn parse_field<T: FromStr>(
+    line: &str,
+    span: Range<usize>,
+    type_name: &str,
+) -> Result<T, ParseError> {
+    line[span.clone()]
+        .parse()
+        .map_err(|_| ParseError::new(line, span, format!("`{type_name}`")))
+}
+
+/// A field that can be parsed with the help of an [`Interner`], see [`parse_line!`](crate::parse_line).
+/// Implemented for all [`FromStr`] types, which ignore the interner, and for [`Symbol`], which interns the field.
+pub trait FromField: Sized {
+    fn from_field(field: &str, interner: &mut Interner) -> Option<Self>;
+}
+
+impl<T: FromStr> FromField for T {
+    fn from_field(field: &str, _: &mut Interner) -> Option<Self> {
+        field.parse().ok()
+    }
+}
+
+/// Interns a single word, like [`Word`] accepts.
+impl FromField for Symbol {
+    fn from_field(field: &str, interner: &mut Interner) -> Option<Self> {
+        Word::from_str(field)
+            .ok()
+            .map(|word| interner.intern(&word))
+    }
+}
+
+/// Parses a field of `line` as `T`, interning names into `interner`.
+pub fn parse_field_with<T: FromField>(
+    line: &str,
+    span: Range<usize>,
+    type_name: &str,
+    interner: &mut Interner,
+) -> Result<T, ParseError> {
+    T::from_field(&line[span.clone()], interner)
+        .ok_or_else(|| ParseError::new(line, span, format!("`{type_name}`")))
+}
+
+/// Parses a line that follows a pattern into a tuple of typed fields.
+///
+/// Each `{}` in the pattern is a field, parsed with [`FromStr`] as the type at the same position.
+/// Returns a [`ParseError`] describing the first mismatch if the line does not follow the pattern.
+///
+/// Given an [`Interner`] after the line, [`Symbol`] fields are interned into it, so names become dense ids.
+///
+/// ```ignore
+/// use advent_of_code::parse::Word;
+///
+/// let (who, change, by, next_to) = parse_line!(
+///     "{} would {} {} happiness units by sitting next to {}.",
+///     line => String, Word, i32, String
+/// )?;
+///
+/// let (from, to, distance) = parse_line!("{} to {} = {}", line, &mut interner => Symbol, Symbol, u32)?;
+/// ```
+#[macro_export]
+macro_rules! parse_line {
+    ($pattern:literal, $line:expr, $interner:expr => $($ty:ty),+ $(,)?) => {
+        (|| -> Result<($($ty,)+), $crate::parse::ParseError> {
+            let line: &str = $line;
+            let interner: &mut $crate::intern::Interner = $interner;
+            let count = [$(stringify!($ty)),+].len();
+            let mut spans = $crate::parse::split_fields($pattern, line, count)?.into_iter();
+            Ok(($(
+                $crate::parse::parse_field_with::<$ty>(
+                    line,
+                    spans.next().unwrap(),
+                    stringify!($ty),
+                    interner,
+                )?,
+            )+))
+        })()
+    };
+    ($pattern:literal, $line:expr => $($ty:ty),+ $(,)?) => {
+        (|| -> Result<($($ty,)+), $crate::parse::ParseError> {
+            let line: &str = $line;
+            let count = [$(stringify!($ty)),+].len();
+            let mut spans = $crate::parse::split_fields($pattern, line, count)?.into_iter();
+            Ok(($(
+                $crate::parse::parse_field::<$ty>(line, spans.next().unwrap(), stringify!($ty))?,
+            )+))
+        })()
+    };
+}
+
+/// Parses every line of an input with [`parse_line!`] into a `Vec` of tuples, stopping at the first error.
+/// Errors are tagged with the number of the line they occurred on.
+#[macro_export]
+macro_rules! parse_lines {
+    ($pattern:literal, $input:expr, $interner:expr => $($ty:ty),+ $(,)?) => {{
+        let interner: &mut $crate::intern::Interner = $interner;
+        $crate::parse::lines($input, |line| {
+            $crate::parse_line!($pattern, line, &mut *interner => $($ty),+)
+        })
+    }};
+    ($pattern:literal, $input:expr => $($ty:ty),+ $(,)?) => {
+        $crate::parse::lines($input, |line| $crate::parse_line!($pattern, line => $($ty),+))
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ParseError, Word};
+    use crate::intern::{Interner, Symbol};
+
+    #[test]
+    fn parses_typed_fields() {
+        let line = "Alice would lose 79 happiness units by sitting next to Carol.";
+        let parsed = parse_line!(
+            "{} would {} {} happiness units by sitting next to {}.",
+            line => String, Word, i32, String
+        );
+        assert_eq!(
+            parsed,
+            Ok((
+                "Alice".to_string(),
+                Word("lose".into()),
+                79,
+                "Carol".to_string()
+            ))
+        );
+
+        assert_eq!(
+            parse_line!("{}x{}x{}", "2x3x4" => u32, u32, u32),
+            Ok((2, 3, 4))
+        );
+    }
+
+    #[test]
+    fn describes_mismatches() {
+        let error = parse_line!("{} to {} = {}", "London to Dublin = far" => String, String, u32)
+            .unwrap_err();
+        assert_eq!(error.span, 19..22);
+        assert_eq!(error.found(), "far");
+
+        let error = parse_line!("{} to {} = {}", "London - Dublin = 464" => String, String, u32)
+            .unwrap_err();
+        assert_eq!(error.expected, "` to `");
+
+        let error = parse_line!("toggle {},{}", "turn on 1,2" => u32, u32).unwrap_err();
+        assert_eq!(error.found(), "turn on");
+
+        let error = parse_line!("{} {}.", "one two" => Word, Word).unwrap_err();
+        assert_eq!(error.expected, "`.` at the end");
+
+        assert!(parse_line!("{} is {}", "a b is c" => Word, Word).is_err());
+    }
+
+    #[test]
+    fn parses_all_lines() {
+        let input = "a to b = 1\nb to c = 2\n";
+        assert_eq!(
+            parse_lines!("{} to {} = {}", input => Word, Word, u32).map(|v| v.len()),
+            Ok(2)
+        );
+
+        let error =
+            parse_lines!("{} to {} = {}", "a to b = 1\na to c = x" => Word, Word, u32).unwrap_err();
+        assert_eq!(error.line_number, Some(2));
+    }
+
+    #[test]
+    fn interns_names() {
+        let mut interner = Interner::new();
+        let edges = parse_lines!(
+            "{} to {} = {}",
+            "London to Dublin = 464\nLondon to Belfast = 518\n",
+            &mut interner => Symbol, Symbol, u32
+        );
+        assert_eq!(
+            edges,
+            Ok(vec![
+                (Symbol(0), Symbol(1), 464),
+                (Symbol(0), Symbol(2), 518)
+            ])
+        );
+        assert_eq!(&interner[Symbol(2)], "Belfast");
+
+        let error =
+            parse_line!("{} to {}", "London to  ", &mut interner => Symbol, Symbol).unwrap_err();
+        assert_eq!(error.expected, "`Symbol`");
+    }
+
+    #[test]
+    fn renders_annotated_snippets() {
+        let error = ParseError::new("London to Dublin = far", 19..22, "`u32`").at_line(12);
+        let expected = [
+            "expected `u32`, found `far` at line 12, column 20",
+            "   |",
+            "12 | London to Dublin = far",
+            "   |                    ^^^",
+        ]
+        .join("\n");
+        assert_eq!(error.to_string(), expected);
+
+        let line = "turn sideways 0,0 through 2,2";
+        let (kind, _) = line.split_at(13);
+        let error = ParseError::at(line, kind, "`turn on`, `turn off` or `toggle`");
+        assert_eq!(error.span, 0..13);
+        assert!(error
+            .to_string()
+            .ends_with("\n | turn sideways 0,0 through 2,2\n | ^^^^^^^^^^^^^"));
+
+        let error = ParseError::new("1x2", 3..3, "`x`");
+        assert!(error
+            .to_string()
+            .starts_with("expected `x`, found nothing at column 4"));
+    }
+}
@@ -0,0 +1,364 @@
+/// Points in 2D and 3D space, and the four directions on a grid.
+/// Directions follow grid conventions: `y` grows downwards, so [`Direction::Up`] decreases `y`.
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Absolute difference of `a` and `b`, which also works for unsigned integers.
+fn abs_diff<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Implements component-wise arithmetic, multiplication by a scalar and distances for a point type.
+macro_rules! impl_point {
+    ($point:ident { $first:ident $(, $field:ident)* }) => {
+        impl<T> $point<T> {
+            pub const fn new($first: T $(, $field: T)*) -> Self {
+                Self { $first $(, $field)* }
+            }
+        }
+
+        impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> $point<T> {
+            /// Sum of the absolute differences of all coordinates.
+            pub fn manhattan(self, other: Self) -> T {
+                abs_diff(self.$first, other.$first) $(+ abs_diff(self.$field, other.$field))*
+            }
+
+            /// Largest absolute difference of any coordinate.
+            pub fn chebyshev(self, other: Self) -> T {
+                let distance = abs_diff(self.$first, other.$first);
+                $(
+                    let distance = match abs_diff(self.$field, other.$field) {
+                        d if d > distance => d,
+                        _ => distance,
+                    };
+                )*
+                distance
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self {
+                    $first: self.$first + rhs.$first,
+                    $($field: self.$field + rhs.$field,)*
+                }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self {
+                    $first: self.$first - rhs.$first,
+                    $($field: self.$field - rhs.$field,)*
+                }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                self.$first += rhs.$first;
+                $(self.$field += rhs.$field;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.$first -= rhs.$first;
+                $(self.$field -= rhs.$field;)*
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self {
+                    $first: -self.$first,
+                    $($field: -self.$field,)*
+                }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self {
+                    $first: self.$first * rhs,
+                    $($field: self.$field * rhs,)*
+                }
+            }
+        }
+    };
+}
+
+impl_point!(Point2 { x, y });
+impl_point!(Point3 { x, y, z });
+
+impl Point2<usize> {
+    /// Index of this position in a row-major grid of the given width.
+    pub fn to_index(self, width: usize) -> usize {
+        self.y * width + self.x
+    }
+
+    /// Position of `index` in a row-major grid of the given width.
+    pub fn from_index(index: usize, width: usize) -> Self {
+        Self::new(index % width, index / width)
+    }
+
+    /// Moves one step in `direction`, if that does not leave the positive quadrant.
+    pub fn checked_step(self, direction: Direction) -> Option<Self> {
+        let (dx, dy) = direction.offset();
+        Some(Self::new(
+            self.x.checked_add_signed(dx)?,
+            self.y.checked_add_signed(dy)?,
+        ))
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise from [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Turns by 90° clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// Turns by 90° counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    /// Turns by 180°.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// Change of `(x, y)` when moving one step, as accepted by [`Grid::offset`](crate::grid::Grid::offset).
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    /// Change of a point when moving one step.
+    pub fn delta<T: From<i8>>(self) -> Point2<T> {
+        let (dx, dy) = self.offset();
+        Point2::new(T::from(dx as i8), T::from(dy as i8))
+    }
+}
+
+/// Moves a point one step in a direction.
+impl<T: Add<Output = T> + From<i8>> Add<Direction> for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl<T: AddAssign + From<i8>> AddAssign<Direction> for Point2<T> {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self += rhs.delta();
+    }
+}
+
+/// Error returned when parsing an unknown direction.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError(pub String);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown direction `{}`, expected an arrow (`^>v<`), a letter (`URDL`) or a compass point (`NESW`).",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+/// Parses arrows (`^>v<`), letters (`URDL`) and compass points (`NESW`), in upper or lower case.
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'U' | 'u' | 'N' | 'n' => Ok(Direction::Up),
+            '>' | 'R' | 'r' | 'E' | 'e' => Ok(Direction::Right),
+            'v' | 'D' | 'd' | 'S' | 's' => Ok(Direction::Down),
+            '<' | 'L' | 'l' | 'W' | 'w' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(value.to_string())),
+        }
+    }
+}
+
+/// Parses single characters like [`Direction::try_from`], and names like `up` or `north` in any case.
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Direction::try_from(c).map_err(|_| ParseDirectionError(s.into()));
+        }
+
+        match s.to_lowercase().as_str() {
+            "up" | "north" => Ok(Direction::Up),
+            "right" | "east" => Ok(Direction::Right),
+            "down" | "south" => Ok(Direction::Down),
+            "left" | "west" => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(s.into())),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Point2, Point3};
+
+    #[test]
+    fn computes_with_points() {
+        let a = Point2::new(1, 5);
+        let b = Point2::new(4, -1);
+        assert_eq!(a + b, Point2::new(5, 4));
+        assert_eq!(a - b, Point2::new(-3, 6));
+        assert_eq!(-a * 2, Point2::new(-2, -10));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+
+        let mut c = Point3::new(1_u32, 2, 3);
+        c += Point3::new(1, 1, 1);
+        assert_eq!(c, Point3::from((2, 3, 4)));
+        assert_eq!(c.manhattan(Point3::default()), 9);
+        assert_eq!(Point3::new(0_u32, 7, 2).chebyshev(c), 4);
+    }
+
+    #[test]
+    fn converts_grid_indices() {
+        let point = Point2::new(2_usize, 3);
+        assert_eq!(point.to_index(5), 17);
+        assert_eq!(Point2::from_index(17, 5), point);
+        assert_eq!(<(usize, usize)>::from(point), (2, 3));
+        assert_eq!(point.checked_step(Direction::Up), Some(Point2::new(2, 2)));
+        assert_eq!(Point2::new(0_usize, 0).checked_step(Direction::Left), None);
+    }
+
+    #[test]
+    fn moves_and_rotates() {
+        let mut point = Point2::new(0_i32, 0);
+        point += Direction::Right;
+        assert_eq!(point + Direction::Up, Point2::new(1, -1));
+
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert!(Direction::ALL
+            .iter()
+            .all(|d| d.turn_left().turn_right() == *d));
+    }
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!(Direction::try_from('^'), Ok(Direction::Up));
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert_eq!(Direction::try_from('W'), Ok(Direction::Left));
+        assert_eq!("R".parse(), Ok(Direction::Right));
+        assert_eq!("North".parse(), Ok(Direction::Up));
+        assert!("x".parse::<Direction>().is_err());
+        assert!(Direction::try_from('\n').is_err());
+    }
+}
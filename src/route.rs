@@ -0,0 +1,339 @@
+/// Shortest and longest Hamiltonian paths and cycles through a set of named places.
+/// Routes are found with the Held-Karp dynamic programming algorithm in `O(2ⁿ·n²)`, instead of trying all `n!` orderings.
+use std::ops::Add;
+
+use crate::intern::{Interner, Symbol};
+
+/// Weights of the edges between places, stored as a dense matrix.
+/// Edges are directed, use [`DistanceMatrix::add_symmetric`] for edges that weigh the same in both directions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistanceMatrix<W> {
+    names: Interner,
+    weights: Vec<Option<W>>,
+}
+
+/// Whether a route should minimise or maximise its total weight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Goal {
+    Shortest,
+    Longest,
+}
+
+impl Goal {
+    fn is_better<W: Ord>(self, candidate: W, current: W) -> bool {
+        match self {
+            Goal::Shortest => candidate < current,
+            Goal::Longest => candidate > current,
+        }
+    }
+}
+
+/// A route through all places, with its total weight.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route<W> {
+    pub value: W,
+    /// Indices of the places in the order they are visited. For cycles, the first place is not repeated at the end.
+    pub order: Vec<usize>,
+}
+
+impl<W> Route<W> {
+    /// Names of the places in the order they are visited.
+    pub fn names<'a>(&self, matrix: &'a DistanceMatrix<W>) -> Vec<&'a str> {
+        self.order.iter().map(|i| matrix.name(*i)).collect()
+    }
+}
+
+impl<W> Default for DistanceMatrix<W> {
+    fn default() -> Self {
+        Self {
+            names: Interner::new(),
+            weights: vec![],
+        }
+    }
+}
+
+impl<W> DistanceMatrix<W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names of the places, whose ids are their indices in the matrix.
+    pub fn places(&self) -> &Interner {
+        &self.names
+    }
+
+    /// Number of places.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, index: usize) -> &str {
+        &self.names[Symbol(index as u32)]
+    }
+
+    /// Index of the place called `name`, if it is known.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.names.get(name).map(Symbol::index)
+    }
+}
+
+impl<W: Copy> DistanceMatrix<W> {
+    /// Creates a matrix without edges between the places interned in `names`.
+    pub fn with_places(names: Interner) -> Self {
+        Self {
+            weights: vec![None; names.len() * names.len()],
+            names,
+        }
+    }
+
+    /// Builds a matrix from directed `(from, to, weight)` edges.
+    pub fn from_edges<S: AsRef<str>>(edges: impl IntoIterator<Item = (S, S, W)>) -> Self {
+        let mut matrix = Self::new();
+        for (from, to, weight) in edges {
+            matrix.add_edge(from.as_ref(), to.as_ref(), weight);
+        }
+        matrix
+    }
+
+    /// Builds a matrix from `(a, b, weight)` edges that weigh the same in both directions.
+    pub fn from_symmetric_edges<S: AsRef<str>>(edges: impl IntoIterator<Item = (S, S, W)>) -> Self {
+        let mut matrix = Self::new();
+        for (a, b, weight) in edges {
+            matrix.add_symmetric(a.as_ref(), b.as_ref(), weight);
+        }
+        matrix
+    }
+
+    /// Index of the place called `name`, adding it if it is not known yet.
+    pub fn insert(&mut self, name: &str) -> usize {
+        if let Some(index) = self.position(name) {
+            return index;
+        }
+
+        let len = self.len();
+        let mut weights = vec![None; (len + 1) * (len + 1)];
+        for from in 0..len {
+            weights[from * (len + 1)..from * (len + 1) + len]
+                .copy_from_slice(&self.weights[from * len..(from + 1) * len]);
+        }
+        self.weights = weights;
+        self.names.intern(name).index()
+    }
+
+    /// Weight of the edge from place `from` to place `to`.
+    pub fn get(&self, from: usize, to: usize) -> Option<W> {
+        self.weights[from * self.len() + to]
+    }
+
+    /// Sets the weight of the edge from `from` to `to`, adding both places if needed.
+    pub fn add_edge(&mut self, from: &str, to: &str, weight: W) {
+        let (from, to) = (self.insert(from), self.insert(to));
+        let len = self.len();
+        self.weights[from * len + to] = Some(weight);
+    }
+
+    /// Sets the weight of the edges between `a` and `b` in both directions.
+    pub fn add_symmetric(&mut self, a: &str, b: &str, weight: W) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    /// Sets the weight of the edge between two known places, e.g. ids parsed with [`parse_lines!`](crate::parse_lines).
+    ///
+    /// # Panics
+    /// If either place is not in the matrix.
+    pub fn set(&mut self, from: Symbol, to: Symbol, weight: W) {
+        let len = self.len();
+        assert!(from.index() < len && to.index() < len, "unknown place");
+        self.weights[from.index() * len + to.index()] = Some(weight);
+    }
+
+    /// Sets the weight of the edges between two known places in both directions.
+    pub fn set_symmetric(&mut self, a: Symbol, b: Symbol, weight: W) {
+        self.set(a, b, weight);
+        self.set(b, a, weight);
+    }
+}
+
+impl<W: Copy + Ord + Default + Add<Output = W>> DistanceMatrix<W> {
+    /// Shortest route that visits every place once, starting and ending anywhere.
+    pub fn shortest_path(&self) -> Option<Route<W>> {
+        self.held_karp(Goal::Shortest, false)
+    }
+
+    /// Longest route that visits every place once, starting and ending anywhere.
+    pub fn longest_path(&self) -> Option<Route<W>> {
+        self.held_karp(Goal::Longest, false)
+    }
+
+    /// Shortest route that visits every place once and returns to its start.
+    pub fn shortest_cycle(&self) -> Option<Route<W>> {
+        self.held_karp(Goal::Shortest, true)
+    }
+
+    /// Longest route that visits every place once and returns to its start.
+    pub fn longest_cycle(&self) -> Option<Route<W>> {
+        self.held_karp(Goal::Longest, true)
+    }
+
+    /// Finds the best route for `goal`, or `None` if no route visits all places over known edges.
+    ///
+    /// `best[mask][last]` holds the best weight of a route that visits the places in `mask` and ends at `last`.
+    /// Cycles start at place `0`, as any place of a cycle can be its start.
+    ///
+    /// # Panics
+    /// If there are more places than bits in a `usize`.
+    pub fn held_karp(&self, goal: Goal, cycle: bool) -> Option<Route<W>> {
+        let n = self.len();
+        if n == 0 {
+            return None;
+        }
+        assert!(
+            n < usize::BITS as usize,
+            "too many places for held-karp: {n}"
+        );
+
+        let full = (1 << n) - 1;
+        let mut best: Vec<Option<W>> = vec![None; (full + 1) * n];
+        let mut parent: Vec<Option<usize>> = vec![None; (full + 1) * n];
+
+        if cycle {
+            best[n] = Some(W::default());
+        } else {
+            (0..n).for_each(|i| best[(1 << i) * n + i] = Some(W::default()));
+        }
+
+        for mask in 1..=full {
+            for last in 0..n {
+                let Some(value) = best[mask * n + last] else {
+                    continue;
+                };
+                for next in (0..n).filter(|next| mask & (1 << next) == 0) {
+                    let Some(weight) = self.get(last, next) else {
+                        continue;
+                    };
+                    let candidate = value + weight;
+                    let slot = (mask | 1 << next) * n + next;
+                    if best[slot].is_none_or(|current| goal.is_better(candidate, current)) {
+                        best[slot] = Some(candidate);
+                        parent[slot] = Some(last);
+                    }
+                }
+            }
+        }
+
+        let (last, value) = (0..n)
+            .filter_map(|last| {
+                let value = best[full * n + last]?;
+                match cycle && n > 1 {
+                    true => Some((last, value + self.get(last, 0)?)),
+                    false => Some((last, value)),
+                }
+            })
+            .reduce(|a, b| if goal.is_better(b.1, a.1) { b } else { a })?;
+
+        let mut order = vec![last];
+        let mut mask = full;
+        let mut node = last;
+        while let Some(previous) = parent[mask * n + node] {
+            mask &= !(1 << node);
+            node = previous;
+            order.push(node);
+        }
+        order.reverse();
+
+        Some(Route { value, order })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DistanceMatrix;
+    use crate::intern::Interner;
+
+    fn get_mock_matrix() -> DistanceMatrix<u32> {
+        DistanceMatrix::from_symmetric_edges([
+            ("London", "Dublin", 464),
+            ("London", "Belfast", 518),
+            ("Dublin", "Belfast", 141),
+        ])
+    }
+
+    #[test]
+    fn builds_matrices() {
+        let mut matrix = get_mock_matrix();
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.position("Belfast"), Some(2));
+        assert_eq!(matrix.get(1, 0), Some(464));
+        assert_eq!(matrix.get(0, 0), None);
+
+        matrix.add_edge("Belfast", "Paris", 7);
+        assert_eq!(matrix.get(2, 3), Some(7));
+        assert_eq!(matrix.get(3, 2), None);
+        assert_eq!(matrix.get(1, 2), Some(141));
+    }
+
+    #[test]
+    fn builds_matrices_from_ids() {
+        let mut names = Interner::new();
+        let (a, b) = (names.intern("a"), names.intern("b"));
+        let mut matrix = DistanceMatrix::with_places(names);
+        matrix.set_symmetric(a, b, 3);
+        assert_eq!(matrix.get(1, 0), Some(3));
+        assert_eq!(matrix.position("b"), Some(1));
+        assert_eq!(matrix.shortest_cycle().unwrap().value, 6);
+    }
+
+    #[test]
+    fn finds_paths() {
+        let matrix = get_mock_matrix();
+
+        let shortest = matrix.shortest_path().unwrap();
+        assert_eq!(shortest.value, 605);
+        assert!([
+            ["London", "Dublin", "Belfast"],
+            ["Belfast", "Dublin", "London"]
+        ]
+        .contains(&shortest.names(&matrix)[..].try_into().unwrap()));
+
+        let longest = matrix.longest_path().unwrap();
+        assert_eq!(longest.value, 982);
+        assert_eq!(longest.order.len(), 3);
+    }
+
+    #[test]
+    fn finds_cycles() {
+        // asymmetric: going around clockwise is cheaper than counter-clockwise.
+        let matrix = DistanceMatrix::from_edges([
+            ("a", "b", 1),
+            ("b", "c", 1),
+            ("c", "d", 1),
+            ("d", "a", 1),
+            ("b", "a", 10),
+            ("c", "b", 10),
+            ("d", "c", 10),
+            ("a", "d", 10),
+            ("a", "c", 5),
+            ("c", "a", 5),
+            ("b", "d", 5),
+            ("d", "b", 5),
+        ]);
+
+        let shortest = matrix.shortest_cycle().unwrap();
+        assert_eq!(shortest.value, 4);
+        assert_eq!(shortest.names(&matrix), vec!["a", "b", "c", "d"]);
+        assert_eq!(matrix.longest_cycle().unwrap().value, 40);
+    }
+
+    #[test]
+    fn requires_connected_places() {
+        let mut matrix = get_mock_matrix();
+        matrix.insert("Paris");
+        assert_eq!(matrix.shortest_path(), None);
+        assert_eq!(DistanceMatrix::<u32>::new().shortest_path(), None);
+    }
+}
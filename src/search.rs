@@ -0,0 +1,438 @@
+/// Searches over implicit graphs, given a start state and a function that returns the successors of a state.
+/// Every search counts the nodes it expands and queues. The runner prints the counts of the first execution of a part.
+use std::{
+    cell::Cell,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    ops::Add,
+};
+
+/// Number of nodes a search took off its frontier and put on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Nodes whose successors were generated.
+    pub expanded: u64,
+    /// Nodes that were added to the frontier, including the start.
+    pub queued: u64,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} expanded, {} queued", self.expanded, self.queued)
+    }
+}
+
+thread_local! {
+    static TOTALS: Cell<Stats> = const { Cell::new(Stats { expanded: 0, queued: 0 }) };
+}
+
+fn record(stats: Stats) {
+    TOTALS.with(|totals| {
+        let total = totals.get();
+        totals.set(Stats {
+            expanded: total.expanded + stats.expanded,
+            queued: total.queued + stats.queued,
+        });
+    });
+}
+
+/// Counters of all searches on this thread at the start of a measured section.
+pub struct Snapshot(Stats);
+
+impl Snapshot {
+    pub fn start() -> Self {
+        Self(TOTALS.with(Cell::get))
+    }
+
+    /// Counts of the searches since the start, or `None` if there were none.
+    pub fn finish(self) -> Option<Stats> {
+        let total = TOTALS.with(Cell::get);
+        let stats = Stats {
+            expanded: total.expanded - self.0.expanded,
+            queued: total.queued - self.0.queued,
+        };
+        (stats != Stats::default()).then_some(stats)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Strategy for skipping states that were already visited.
+pub trait Visited<S> {
+    /// Marks `state` as visited. Returns whether it was not visited before.
+    fn insert(&mut self, state: &S) -> bool;
+
+    /// Whether `state` was visited.
+    fn contains(&self, state: &S) -> bool;
+}
+
+/// Remembers visited states in a [`HashSet`].
+#[derive(Clone, Debug)]
+pub struct HashVisited<S>(HashSet<S>);
+
+impl<S> Default for HashVisited<S> {
+    fn default() -> Self {
+        Self(HashSet::new())
+    }
+}
+
+impl<S> HashVisited<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: Clone + Hash + Eq> Visited<S> for HashVisited<S> {
+    fn insert(&mut self, state: &S) -> bool {
+        !self.0.contains(state) && self.0.insert(state.clone())
+    }
+
+    fn contains(&self, state: &S) -> bool {
+        self.0.contains(state)
+    }
+}
+
+/// Remembers a key of each visited state, so states with the same key are only visited once.
+/// Useful to ignore parts of a state that do not affect the outcome, or to merge symmetric states.
+pub struct KeyVisited<K, F> {
+    keys: HashSet<K>,
+    key: F,
+}
+
+impl<K, F> KeyVisited<K, F> {
+    pub fn new(key: F) -> Self {
+        Self {
+            keys: HashSet::new(),
+            key,
+        }
+    }
+}
+
+impl<S, K: Hash + Eq, F: Fn(&S) -> K> Visited<S> for KeyVisited<K, F> {
+    fn insert(&mut self, state: &S) -> bool {
+        self.keys.insert((self.key)(state))
+    }
+
+    fn contains(&self, state: &S) -> bool {
+        self.keys.contains(&(self.key)(state))
+    }
+}
+
+/// Remembers nothing, which turns a search into a tree search.
+/// Only terminates if the goal is reachable or the state space is finite and acyclic,
+/// but saves hashing when states rarely repeat.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoVisited;
+
+impl<S> Visited<S> for NoVisited {
+    fn insert(&mut self, _: &S) -> bool {
+        true
+    }
+
+    fn contains(&self, _: &S) -> bool {
+        false
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A path from the start to a goal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// States from the start to the goal, both included.
+    pub states: Vec<S>,
+}
+
+/// Outcome of a search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Search<S, C> {
+    /// Path to the first goal found, or `None` if no goal was reachable.
+    pub path: Option<Path<S, C>>,
+    pub stats: Stats,
+}
+
+impl<S, C: Copy> Search<S, C> {
+    /// Cost of the path to the goal, if one was found.
+    pub fn cost(&self) -> Option<C> {
+        self.path.as_ref().map(|path| path.cost)
+    }
+}
+
+/// Searched states, each with the index of the state it was reached from.
+struct Tree<S> {
+    nodes: Vec<(S, Option<usize>)>,
+}
+
+impl<S: Clone> Tree<S> {
+    fn push(&mut self, state: S, parent: Option<usize>) -> usize {
+        self.nodes.push((state, parent));
+        self.nodes.len() - 1
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<S> {
+        let mut states = vec![self.nodes[index].0.clone()];
+        while let Some(parent) = self.nodes[index].1 {
+            states.push(self.nodes[parent].0.clone());
+            index = parent;
+        }
+        states.reverse();
+        states
+    }
+}
+
+fn finish<S, C>(path: Option<Path<S, C>>, stats: Stats) -> Search<S, C> {
+    record(stats);
+    Search { path, stats }
+}
+
+/// Breadth-first search for the path with the fewest steps to a goal. The cost is the number of steps.
+/// States are marked as visited when they are queued.
+pub fn bfs<S: Clone, I: IntoIterator<Item = S>>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    mut visited: impl Visited<S>,
+) -> Search<S, usize> {
+    let mut stats = Stats::default();
+    let mut tree = Tree { nodes: vec![] };
+
+    visited.insert(&start);
+    tree.push(start, None);
+    stats.queued += 1;
+
+    // the tree doubles as the queue: nodes are appended in the order they are reached.
+    let mut next = 0;
+    while next < tree.nodes.len() {
+        let index = next;
+        next += 1;
+
+        if is_goal(&tree.nodes[index].0) {
+            let states = tree.path_to(index);
+            let path = Path {
+                cost: states.len() - 1,
+                states,
+            };
+            return finish(Some(path), stats);
+        }
+
+        stats.expanded += 1;
+        for successor in successors(&tree.nodes[index].0) {
+            if visited.insert(&successor) {
+                tree.push(successor, Some(index));
+                stats.queued += 1;
+            }
+        }
+    }
+
+    finish(None, stats)
+}
+
+/// Dijkstra's algorithm for the cheapest path to a goal. Successors are returned with the cost of the step to them.
+/// States are marked as visited when they are expanded, so a state can be queued more than once.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+    visited: impl Visited<S>,
+) -> Search<S, C>
+where
+    S: Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal, visited)
+}
+
+/// A* search for the cheapest path to a goal, guided by a `heuristic` that estimates the remaining cost.
+/// The path is the cheapest if the heuristic never overestimates and never drops by more than the cost of a step.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+    mut visited: impl Visited<S>,
+) -> Search<S, C>
+where
+    S: Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut stats = Stats::default();
+    let mut tree = Tree { nodes: vec![] };
+    let mut frontier = BinaryHeap::new();
+
+    let estimate = heuristic(&start);
+    let index = tree.push(start, None);
+    frontier.push(Reverse((estimate, C::default(), index)));
+    stats.queued += 1;
+
+    while let Some(Reverse((_, cost, index))) = frontier.pop() {
+        if !visited.insert(&tree.nodes[index].0) {
+            continue;
+        }
+
+        if is_goal(&tree.nodes[index].0) {
+            let path = Path {
+                cost,
+                states: tree.path_to(index),
+            };
+            return finish(Some(path), stats);
+        }
+
+        stats.expanded += 1;
+        for (successor, step) in successors(&tree.nodes[index].0) {
+            if visited.contains(&successor) {
+                continue;
+            }
+            let cost = cost + step;
+            let estimate = cost + heuristic(&successor);
+            let successor = tree.push(successor, Some(index));
+            frontier.push(Reverse((estimate, cost, successor)));
+            stats.queued += 1;
+        }
+    }
+
+    finish(None, stats)
+}
+
+/// Iterative-deepening depth-first search for the path with the fewest steps to a goal, up to `max_depth` steps.
+/// Uses memory proportional to the depth instead of the number of states, at the cost of revisiting shallow states.
+/// Cycles are avoided by skipping states that are on the current path. The cost is the number of steps.
+pub fn iddfs<S: Clone + PartialEq, I: IntoIterator<Item = S>>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    max_depth: usize,
+) -> Search<S, usize> {
+    let mut stats = Stats {
+        expanded: 0,
+        queued: 1,
+    };
+
+    if is_goal(&start) {
+        let path = Path {
+            cost: 0,
+            states: vec![start],
+        };
+        return finish(Some(path), stats);
+    }
+
+    for limit in 1..=max_depth {
+        let mut path = vec![start.clone()];
+        let mut stack = vec![successors(&start).into_iter()];
+        stats.expanded += 1;
+        let mut is_cut = false;
+
+        while let Some(children) = stack.last_mut() {
+            let Some(child) = children.next() else {
+                stack.pop();
+                path.pop();
+                continue;
+            };
+            stats.queued += 1;
+
+            if path.contains(&child) {
+                continue;
+            }
+            if is_goal(&child) {
+                path.push(child);
+                let path = Path {
+                    cost: path.len() - 1,
+                    states: path,
+                };
+                return finish(Some(path), stats);
+            }
+            if path.len() < limit {
+                stats.expanded += 1;
+                stack.push(successors(&child).into_iter());
+                path.push(child);
+            } else {
+                is_cut = true;
+            }
+        }
+
+        // no state was deeper than the limit, so there is nothing left to find.
+        if !is_cut {
+            break;
+        }
+    }
+
+    finish(None, stats)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, dijkstra, iddfs, HashVisited, KeyVisited, NoVisited, Snapshot};
+
+    /// Steps on a number line: `+1`, `-1` and `*2`.
+    fn steps(n: &i32) -> Vec<i32> {
+        vec![n + 1, n - 1, n * 2]
+    }
+
+    #[test]
+    fn finds_fewest_steps() {
+        let search = bfs(1, steps, |n| *n == 10, HashVisited::new());
+        let path = search.path.unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.states.first(), Some(&1));
+        assert_eq!(path.states.last(), Some(&10));
+        assert!(search.stats.expanded > 0);
+
+        let search = iddfs(1, steps, |n| *n == 10, 10);
+        assert_eq!(search.cost(), Some(4));
+
+        assert_eq!(iddfs(1, steps, |n| *n == 10, 3).path, None);
+        assert_eq!(bfs(1, |_| vec![], |n| *n == 10, NoVisited).path, None);
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        // doubling costs 5, so walking from 3 to 6 is cheaper.
+        let weighted = |n: &i32| vec![(n + 1, 1), (n - 1, 1), (n * 2, 5)];
+
+        let search = dijkstra(3, weighted, |n| *n == 6, HashVisited::new());
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.path.unwrap().states, vec![3, 4, 5, 6]);
+
+        let search = astar(
+            3,
+            weighted,
+            |n| (12 - n).max(0) / 2,
+            |n| *n == 12,
+            HashVisited::new(),
+        );
+        assert_eq!(search.cost(), Some(8));
+    }
+
+    #[test]
+    fn merges_states_by_key() {
+        // states are (position, moves); only the position matters.
+        let search = bfs(
+            (0, 0),
+            |(n, moves): &(i32, u32)| [(n + 1, moves + 1), (n - 1, moves + 1)],
+            |(n, _)| *n == 3,
+            KeyVisited::new(|(n, _): &(i32, u32)| *n),
+        );
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.stats.queued, 7);
+    }
+
+    #[test]
+    fn counts_searches() {
+        let snapshot = Snapshot::start();
+        assert_eq!(Snapshot::start().finish(), None);
+
+        let search = bfs(1, steps, |n| *n == 10, HashVisited::new());
+        let other = bfs(1, steps, |n| *n == 3, HashVisited::new());
+
+        let stats = snapshot.finish().unwrap();
+        assert_eq!(stats.expanded, search.stats.expanded + other.stats.expanded);
+        assert_eq!(
+            stats.to_string(),
+            format!("{} expanded, {} queued", stats.expanded, stats.queued)
+        );
+    }
+}
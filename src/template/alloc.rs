@@ -0,0 +1,155 @@
+/// A global allocator that counts heap allocations, as a lightweight alternative to DHAT.
+/// It is installed by the `solution!` macro when the `count-allocs` feature is enabled.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and counts allocations, allocated bytes and the peak heap size.
+pub struct CountingAlloc;
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    BYTES.fetch_add(size as u64, Relaxed);
+    grow(size);
+}
+
+fn grow(size: usize) {
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+fn shrink(size: usize) {
+    CURRENT.fetch_sub(size, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            BYTES.fetch_add(new_size as u64, Relaxed);
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a section of code.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Peak heap size above the heap size at the start of the section, in bytes.
+    pub peak: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} B, peak {} B",
+            self.allocations, self.bytes, self.peak
+        )
+    }
+}
+
+/// Counters at the start of a measured section. Resets the peak heap size, so sections must not overlap.
+pub struct Snapshot {
+    allocations: u64,
+    bytes: u64,
+    base: usize,
+}
+
+impl Snapshot {
+    pub fn start() -> Self {
+        let base = CURRENT.load(Relaxed);
+        PEAK.store(base, Relaxed);
+        Self {
+            allocations: ALLOCATIONS.load(Relaxed),
+            bytes: BYTES.load(Relaxed),
+            base,
+        }
+    }
+
+    pub fn finish(self) -> AllocStats {
+        AllocStats {
+            allocations: ALLOCATIONS.load(Relaxed) - self.allocations,
+            bytes: BYTES.load(Relaxed) - self.bytes,
+            peak: PEAK.load(Relaxed).saturating_sub(self.base) as u64,
+        }
+    }
+}
+
+/// Parses stats printed with [`Display`] from the fields of a runner output line, e.g. `["3 allocs", "96 B", "peak 64 B"]`.
+pub fn parse_stats<'a>(fields: impl Iterator<Item = &'a str>) -> Option<AllocStats> {
+    let mut stats = AllocStats::default();
+    let mut found = false;
+
+    for field in fields {
+        let field = field.trim();
+        if let Some(n) = field.strip_suffix(" allocs") {
+            stats.allocations = n.parse().ok()?;
+            found = true;
+        } else if let Some(n) = field
+            .strip_prefix("peak ")
+            .and_then(|f| f.strip_suffix(" B"))
+        {
+            stats.peak = n.parse().ok()?;
+        } else if let Some(n) = field.strip_suffix(" B") {
+            stats.bytes = n.parse().ok()?;
+        }
+    }
+
+    found.then_some(stats)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_stats, AllocStats};
+
+    #[test]
+    fn roundtrips_stats() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 96,
+            peak: 64,
+        };
+        assert_eq!(stats.to_string(), "3 allocs, 96 B, peak 64 B");
+        assert_eq!(parse_stats(stats.to_string().split(", ")), Some(stats));
+        assert_eq!(parse_stats(["1.2ms @ 10 samples"].into_iter()), None);
+    }
+}
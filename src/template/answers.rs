@@ -0,0 +1,201 @@
+/// Ledger of answers that were accepted by adventofcode.com, used to track stars locally.
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::Day;
+
+pub(crate) static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Version of the `answers.json` schema written by this crate.
+const SCHEMA_VERSION: u64 = 1;
+
+/// Stars that can be collected in one event.
+pub const MAX_STARS: u8 = 50;
+
+/// Accepted answers of a single day.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    pub day: Day,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<String>,
+}
+
+impl DayAnswers {
+    fn new(day: Day) -> Self {
+        Self {
+            day,
+            part_1: None,
+            part_2: None,
+        }
+    }
+}
+
+/// Accepted answers of all days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+/// On-disk representation of [`Answers`].
+#[derive(Serialize, Deserialize)]
+struct AnswersFile {
+    version: u64,
+    data: Vec<DayAnswers>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let file = AnswersFile {
+            version: SCHEMA_VERSION,
+            data: self.data.clone(),
+        };
+        // serializing plain structs to a string does not fail.
+        fs::write(
+            ANSWERS_FILE_PATH,
+            serde_json::to_string_pretty(&file).unwrap() + "\n",
+        )
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(ANSWERS_FILE_PATH) else {
+            return Answers::default();
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Records the accepted answer of a part.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(DayAnswers::new(day));
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => entry.part_1 = Some(answer.into()),
+            2 => entry.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Returns the accepted answer of a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let entry = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => entry.part_1.as_deref(),
+            2 => entry.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Stars collected on a day.
+    /// The second star of day 25 has no puzzle, it is awarded once all other stars are collected.
+    pub fn stars(&self, day: Day) -> u8 {
+        let solved = |part| u8::from(self.get(day, part).is_some());
+
+        if day == 25 && solved(1) == 1 {
+            let others: u8 = self
+                .data
+                .iter()
+                .filter(|a| a.day != 25)
+                .map(|a| self.stars(a.day))
+                .sum();
+            return if others == MAX_STARS - 2 { 2 } else { 1 };
+        }
+
+        solved(1) + solved(2)
+    }
+
+    /// Stars collected over all days.
+    pub fn total_stars(&self) -> u8 {
+        self.data.iter().map(|a| self.stars(a.day)).sum()
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let file: AnswersFile =
+            serde_json::from_str(&value).map_err(|e| format!("malformed answers: {e}"))?;
+
+        if file.version > SCHEMA_VERSION {
+            return Err(format!(
+                "unsupported answers version {}, expected at most {SCHEMA_VERSION}.",
+                file.version
+            ));
+        }
+
+        Ok(Answers { data: file.data })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, MAX_STARS};
+    use crate::{day, template::all_days};
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(3), 2, "42");
+        answers.record(day!(1), 1, "7");
+        answers.record(day!(3), 1, "12");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(3), 1), Some("12"));
+        assert_eq!(answers.get(day!(3), 2), Some("42"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.stars(day!(3)), 2);
+        assert_eq!(answers.total_stars(), 3);
+    }
+
+    #[test]
+    fn awards_last_star_with_all_others() {
+        let mut answers = Answers::default();
+        for day in all_days() {
+            answers.record(day, 1, "1");
+        }
+        assert_eq!(answers.stars(day!(25)), 1);
+
+        for day in all_days().filter(|day| *day != 25) {
+            answers.record(day, 2, "2");
+        }
+        assert_eq!(answers.stars(day!(25)), 2);
+        assert_eq!(answers.total_stars(), MAX_STARS);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let json = r#"{
+  "version": 1,
+  "data": [
+    {
+      "day": "01",
+      "part_1": "7"
+    }
+  ]
+}"#;
+        let answers = Answers::try_from(json.to_string()).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("7"));
+        assert!(Answers::try_from(json.replace("1,", "2,")).is_err());
+    }
+}
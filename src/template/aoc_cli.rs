@@ -1,6 +1,8 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
+    path::PathBuf,
     process::{Command, Output, Stdio},
 };
 
@@ -33,6 +35,27 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
+/// Returns the version string reported by `aoc -V`, e.g. `aoc-cli 0.12.0`.
+pub fn version() -> Result<String, AocCommandError> {
+    let output = Command::new("aoc")
+        .arg("-V")
+        .output()
+        .map_err(|_| AocCommandError::CommandNotFound)?;
+
+    if !output.status.success() {
+        return Err(AocCommandError::BadExitStatus(output));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Location of the session cookie file that aoc-cli reads by default.
+pub fn session_file_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+}
+
 pub fn read(day: Day) -> Result<Output, AocCommandError> {
     let puzzle_path = get_puzzle_path(day);
 
@@ -77,7 +100,12 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    call_aoc_cli_captured(&args)
+}
+
+/// Returns whether the output of [`submit`] reports the answer as correct.
+pub fn is_accepted(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
 }
 
 fn get_input_path(day: Day) -> String {
@@ -88,8 +116,8 @@ fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
+pub(crate) fn get_year() -> Option<u16> {
+    match env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
     }
@@ -123,3 +151,21 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/// Like [`call_aoc_cli`], but also captures stdout so the response can be inspected.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
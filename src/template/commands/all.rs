@@ -1,5 +1,11 @@
 use crate::template::{all_days, run_multi::run_multi};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, count_allocs: bool) {
+    run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        count_allocs,
+        false,
+    );
 }
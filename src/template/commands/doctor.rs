@@ -0,0 +1,291 @@
+use std::{
+    fs,
+    path::Path,
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::timings::{schema_version, Timings, SCHEMA_VERSION, TIMINGS_FILE_PATH};
+use crate::template::{
+    all_days, aoc_cli, input_path, readme_benchmarks, run_multi, vault, ANSI_BOLD, ANSI_ITALIC,
+    ANSI_RESET,
+};
+
+/// The first year Advent of Code ran.
+const FIRST_YEAR: u16 = 2015;
+
+enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Outcome of a single doctor check, with an optional fix-it hint.
+struct Check {
+    status: Status,
+    message: String,
+    hint: Option<String>,
+}
+
+impl Check {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            status: Status::Ok,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warning(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            status: Status::Warning,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn error(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            status: Status::Error,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn print(&self) {
+        let icon = match self.status {
+            Status::Ok => "✔",
+            Status::Warning => "!",
+            Status::Error => "✖",
+        };
+        println!("{icon} {}", self.message);
+        if let Some(hint) = &self.hint {
+            println!("    {ANSI_ITALIC}hint: {hint}{ANSI_RESET}");
+        }
+    }
+}
+
+pub fn handle() {
+    let sections = [
+        ("aoc-cli", check_aoc_cli()),
+        ("Environment", check_year()),
+        ("Inputs", check_inputs()),
+        ("Timings", check_timings(Path::new(TIMINGS_FILE_PATH))),
+        ("README", check_readme(Path::new("README.md"))),
+    ];
+
+    let mut errors = 0;
+    let mut warnings = 0;
+
+    for (i, (title, checks)) in sections.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}{title}{ANSI_RESET}");
+        for check in checks {
+            match check.status {
+                Status::Ok => {}
+                Status::Warning => warnings += 1,
+                Status::Error => errors += 1,
+            }
+            check.print();
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}{errors} error(s), {warnings} warning(s).{ANSI_RESET}");
+
+    if errors > 0 {
+        process::exit(1);
+    }
+}
+
+fn check_aoc_cli() -> Vec<Check> {
+    let mut checks = vec![match aoc_cli::version() {
+        Ok(version) => Check::ok(format!("`aoc` is callable ({version}).")),
+        Err(e) => Check::warning(
+            format!("`aoc` is not callable: {e}"),
+            "run `cargo install aoc-cli --version 0.12.0` to enable download, read and submit.",
+        ),
+    }];
+
+    let has_env_session = std::env::var("ADVENT_OF_CODE_SESSION").is_ok_and(|s| !s.is_empty());
+
+    checks.push(match aoc_cli::session_file_path() {
+        _ if has_env_session => Check::ok("session cookie is set via `ADVENT_OF_CODE_SESSION`."),
+        Some(path) if path.is_file() => match fs::read_to_string(&path) {
+            Ok(s) if !s.trim().is_empty() => {
+                Check::ok(format!("session file found at \"{}\".", path.display()))
+            }
+            _ => Check::error(
+                format!(
+                    "session file \"{}\" is empty or unreadable.",
+                    path.display()
+                ),
+                "paste the `session` cookie from adventofcode.com into the file.",
+            ),
+        },
+        Some(path) => Check::warning(
+            format!("session file \"{}\" does not exist.", path.display()),
+            "create it and paste the `session` cookie from adventofcode.com into it.",
+        ),
+        None => Check::warning(
+            "could not determine the home directory to look for a session file.",
+            "set `HOME` or provide the cookie via `ADVENT_OF_CODE_SESSION`.",
+        ),
+    });
+
+    checks
+}
+
+fn check_year() -> Vec<Check> {
+    let Ok(raw) = std::env::var("AOC_YEAR") else {
+        return vec![Check::warning(
+            "`AOC_YEAR` is not set, aoc-cli will default to the current event.",
+            "set `AOC_YEAR` in the `[env]` section of `.cargo/config.toml`.",
+        )];
+    };
+
+    let current = current_year();
+
+    vec![match aoc_cli::get_year() {
+        Some(year) if (FIRST_YEAR..=current).contains(&year) => {
+            Check::ok(format!("`AOC_YEAR` is {year}."))
+        }
+        _ => Check::error(
+            format!("`AOC_YEAR` is \"{raw}\", which is not a valid event year."),
+            format!("use a year between {FIRST_YEAR} and {current}."),
+        ),
+    }]
+}
+
+fn check_inputs() -> Vec<Check> {
+    let mut checks = vec![];
+
+    for day in all_days() {
+        if !Path::new(&run_multi::get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        let path = input_path("inputs", day, None);
+        match fs::read(&path) {
+            // encrypted inputs are decrypted transparently when read.
+            Err(_) if vault::blob_path(&path).exists() => {}
+            Err(_) => checks.push(Check::error(
+                format!("\"{}\" is missing.", path.display()),
+                format!("run `cargo download {day}` or paste your puzzle input into it."),
+            )),
+            Ok(content) if is_blank(&content) => checks.push(Check::warning(
+                format!("\"{}\" is empty.", path.display()),
+                format!("run `cargo download {day}` or paste your puzzle input into it."),
+            )),
+            Ok(content) if has_crlf(&content) => checks.push(crlf_warning(&path)),
+            Ok(_) => {}
+        }
+
+        // examples are optional, as many tests use inline cases instead.
+        let example_path = input_path("examples", day, None);
+        if fs::read(&example_path).is_ok_and(|content| has_crlf(&content)) {
+            checks.push(crlf_warning(&example_path));
+        }
+    }
+
+    if checks.is_empty() {
+        checks.push(Check::ok("inputs of all scaffolded days look fine."));
+    }
+
+    checks
+}
+
+fn crlf_warning(path: &Path) -> Check {
+    Check::warning(
+        format!("\"{}\" has CRLF line endings.", path.display()),
+        "convert the file to LF line endings, e.g. with `dos2unix`.",
+    )
+}
+
+fn is_blank(content: &[u8]) -> bool {
+    content.iter().all(u8::is_ascii_whitespace)
+}
+
+fn has_crlf(content: &[u8]) -> bool {
+    content.windows(2).any(|w| w == b"\r\n")
+}
+
+fn check_timings(path: &Path) -> Vec<Check> {
+    let check = match fs::read_to_string(path) {
+        Err(_) => Check::ok(format!(
+            "\"{}\" does not exist yet, it is created by `cargo time --store`.",
+            path.display()
+        )),
+        Ok(content) => match Timings::try_from(content.clone()) {
+            Ok(_) if schema_version(&content).is_ok_and(|v| v < SCHEMA_VERSION) => Check::warning(
+                format!(
+                    "\"{}\" uses an outdated schema, it is migrated when read.",
+                    path.display()
+                ),
+                "run `cargo time --store` to upgrade the file.",
+            ),
+            Ok(timings) => Check::ok(format!(
+                "\"{}\" is valid ({} day(s) benched).",
+                path.display(),
+                timings.data.len()
+            )),
+            Err(e) => Check::error(
+                format!(
+                    "\"{}\" does not match the expected schema: {e}",
+                    path.display()
+                ),
+                "fix the file by hand or delete it and re-run `cargo time --all --store`.",
+            ),
+        },
+    };
+
+    vec![check]
+}
+
+fn check_readme(path: &Path) -> Vec<Check> {
+    let check = match fs::read_to_string(path) {
+        Err(e) => Check::error(
+            format!("could not read \"{}\": {e}", path.display()),
+            "run the command from the repository root.",
+        ),
+        Ok(readme) => match readme_benchmarks::check_markers(&readme) {
+            Ok(()) => Check::ok("benchmark table markers are balanced."),
+            Err(e) => Check::error(
+                format!("benchmark table markers are broken: {e}"),
+                "make sure every benchmark table marker, e.g. `<!--- benchmarking table --->`, appears exactly twice.",
+            ),
+        },
+    };
+
+    vec![check]
+}
+
+fn current_year() -> u16 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // average length of a gregorian year, precise enough for a sanity check.
+    #[allow(clippy::cast_possible_truncation)]
+    let year = 1970 + (secs / 31_556_952) as u16;
+    year
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{has_crlf, is_blank};
+
+    #[test]
+    fn flags_blank_content() {
+        assert!(is_blank(b""));
+        assert!(is_blank(b" \n"));
+        assert!(!is_blank(b"1\n"));
+    }
+
+    #[test]
+    fn flags_crlf_content() {
+        assert!(has_crlf(b"1\r\n2\r\n"));
+        assert!(!has_crlf(b"1\n2\n"));
+    }
+}
@@ -0,0 +1,27 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::answers::Answers;
+use crate::template::report::{self, Format};
+
+use crate::template::timings::Timings;
+
+pub use crate::template::report::Format as ReportFormat;
+
+pub fn handle(format: Format, output: Option<PathBuf>) {
+    let report = report::render(
+        format,
+        &Timings::read_from_file(),
+        &Answers::read_from_file(),
+    );
+
+    match output {
+        Some(path) => match fs::write(&path, report) {
+            Ok(()) => println!("Wrote report to \"{}\".", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write report to \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        },
+        None => print!("{report}"),
+    }
+}
@@ -1,6 +1,11 @@
 pub mod all;
+pub mod doctor;
 pub mod download;
+pub mod export;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod stars;
 pub mod time;
+pub mod vault;
@@ -0,0 +1,31 @@
+use std::{
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{run_multi::get_path_for_bin, Day};
+
+pub fn handle(day: Day) {
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet.");
+        process::exit(1);
+    }
+
+    let day_padded = day.to_string();
+    let mut cmd = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &day_padded,
+            "--",
+            "--scale",
+        ])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -1,8 +1,31 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{watch, Day};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    count_allocs: bool,
+    submit_part: Option<u8>,
+    watch: bool,
+) {
+    if watch {
+        // the watcher runs the solution itself and has no use for these.
+        let ignored = [
+            (dhat, "--dhat"),
+            (count_allocs, "--alloc"),
+            (submit_part.is_some(), "--submit"),
+        ];
+        if let Some((_, flag)) = ignored.iter().find(|(is_set, _)| *is_set) {
+            eprintln!("`{flag}` can not be combined with `--watch`.");
+            process::exit(1);
+        }
+
+        watch::handle(day, release);
+        return;
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,6 +39,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if count_allocs && !dhat {
+        cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -0,0 +1,43 @@
+use std::{path::Path, process};
+
+use crate::template::answers::{Answers, MAX_STARS};
+use crate::template::timings::{Timings, TIMINGS_FILE_PATH};
+use crate::template::{aoc_cli, readme_stars};
+
+pub fn handle() {
+    let answers = Answers::read_from_file();
+    let stars = answers.total_stars();
+
+    // the runtime badge is only written once benchmarks have been stored.
+    let total_millis = Path::new(TIMINGS_FILE_PATH)
+        .exists()
+        .then(|| Timings::read_from_file().total_millis());
+
+    let mut failed = false;
+
+    match readme_stars::update(aoc_cli::get_year(), &answers) {
+        Ok(()) => println!("Updated star progress in the readme."),
+        Err(e) => {
+            eprintln!("Failed to update star progress in the readme: {e}");
+            failed = true;
+        }
+    }
+
+    match readme_stars::write_badges(stars, total_millis) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote badge to \"{path}\".");
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to write badges: {e}");
+            failed = true;
+        }
+    }
+
+    println!("⭐ {stars} / {MAX_STARS}");
+
+    if failed {
+        process::exit(1);
+    }
+}
@@ -1,11 +1,40 @@
 use std::collections::HashSet;
 
+use crate::template::fingerprint::Fingerprint;
+use crate::template::readme_benchmarks::TableOptions;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
+use crate::template::timings::{Timings, TIMINGS_FILE_PATH};
 use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub use crate::template::timings::MergePolicy;
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    merge_policy: MergePolicy,
+    table: Option<String>,
+    count_allocs: bool,
+    isolate: bool,
+) {
+    let table_options = match TableOptions::from_env(table) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Invalid `AOC_BENCH_COLUMNS`: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    // storing replaces the file, so a file that can't be read must not be silently treated as empty.
+    let stored_timings = if store {
+        Timings::try_read_from_file().unwrap_or_else(|e| {
+            eprintln!("Refusing to store timings: {e}");
+            eprintln!("Fix or move \"{TIMINGS_FILE_PATH}\", or run without `--store`.");
+            std::process::exit(1);
+        })
+    } else {
+        Timings::read_from_file()
+    };
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,19 +50,32 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, true, true, count_allocs, isolate).unwrap();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
+        let fingerprint = Fingerprint::current(true, count_allocs);
+        timings.tag_machine(&fingerprint);
+
+        for other in stored_timings.other_machines(&fingerprint) {
+            eprintln!();
+            eprintln!(
+                "Warning: stored timings were recorded on a different machine or toolchain, the total may not be comparable:"
+            );
+            for diff in other.diff(&fingerprint) {
+                eprintln!("  {diff}");
+            }
+        }
+
+        let merged_timings = stored_timings.merge(&timings, merge_policy);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, stored_timings, &table_options) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
@@ -0,0 +1,113 @@
+use std::{fs, path::PathBuf, process, str::FromStr};
+
+use crate::template::{data_dir, vault};
+
+pub enum VaultCommand {
+    Encrypt,
+    Decrypt,
+    Keygen,
+}
+
+impl FromStr for VaultCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(VaultCommand::Encrypt),
+            "decrypt" => Ok(VaultCommand::Decrypt),
+            "keygen" => Ok(VaultCommand::Keygen),
+            _ => Err(format!(
+                "unknown vault command `{s}`, expected `encrypt`, `decrypt` or `keygen`."
+            )),
+        }
+    }
+}
+
+/// Folders and file extensions of files that are stored in the vault.
+const VAULT_FILES: [(&str, &str); 2] = [("inputs", "txt"), ("puzzles", "md")];
+
+pub fn handle(command: &VaultCommand) {
+    if let VaultCommand::Keygen = command {
+        match vault::generate_keyfile() {
+            Ok(path) => println!("🎄 Wrote new vault key to \"{}\".", path.display()),
+            Err(e) => {
+                eprintln!("Failed to create vault key: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let key = match vault::load_key() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let mut failed = false;
+
+    for (folder, extension) in VAULT_FILES {
+        let plaintexts = list_files(folder, extension);
+
+        match command {
+            VaultCommand::Encrypt => {
+                for path in plaintexts {
+                    match vault::encrypt_file(&key, &path) {
+                        Ok(true) => println!("Encrypted \"{}\".", path.display()),
+                        Ok(false) => {}
+                        Err(e) => {
+                            eprintln!("Failed to encrypt \"{}\": {e}", path.display());
+                            failed = true;
+                        }
+                    }
+                }
+            }
+            VaultCommand::Decrypt => {
+                let blob_extension = format!("{extension}.{}", vault::EXTENSION);
+                for blob in list_files(folder, &blob_extension) {
+                    // strip the `.vault` suffix to get the plaintext path.
+                    let path = blob.with_extension("");
+                    if path.exists() {
+                        continue;
+                    }
+                    match vault::decrypt_file(&key, &path)
+                        .and_then(|content| fs::write(&path, content).map_err(Into::into))
+                    {
+                        Ok(()) => println!("Decrypted \"{}\".", path.display()),
+                        Err(e) => {
+                            eprintln!("Failed to decrypt \"{}\": {e}", blob.display());
+                            failed = true;
+                        }
+                    }
+                }
+            }
+            VaultCommand::Keygen => unreachable!(),
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Lists files in a data folder with the given extension, e.g. `txt` or `txt.vault`.
+fn list_files(folder: &str, extension: &str) -> Vec<PathBuf> {
+    let suffix = format!(".{extension}");
+    let Ok(entries) = fs::read_dir(data_dir().join(folder)) else {
+        return vec![];
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|name| name.to_string_lossy().ends_with(&suffix))
+        })
+        .collect();
+
+    files.sort();
+    files
+}
@@ -0,0 +1,161 @@
+/// Description of the machine and toolchain that benchmarks were recorded with.
+use std::{fs, process::Command, thread};
+
+use serde::{Deserialize, Serialize};
+
+/// FNV-1a hash of `bytes`, as std's hashers are not guaranteed to be stable across releases.
+pub fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes
+        .into_iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub cpu_model: String,
+    pub cores: usize,
+    pub rustc: String,
+    pub target: String,
+    pub profile: String,
+    pub features: Vec<String>,
+}
+
+impl Fingerprint {
+    /// Collects the fingerprint of the current machine for solutions built with the given profile,
+    /// and with the counting allocator if `count_allocs` is set.
+    pub fn current(is_release: bool, count_allocs: bool) -> Self {
+        let rustc = Command::new("rustc")
+            .arg("-vV")
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default();
+
+        Self {
+            cpu_model: cpu_model().unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(1, usize::from),
+            rustc: rustc.lines().next().unwrap_or("unknown").to_string(),
+            target: rustc
+                .lines()
+                .find_map(|l| l.strip_prefix("host: "))
+                .unwrap_or("unknown")
+                .to_string(),
+            profile: if is_release { "release" } else { "dev" }.into(),
+            features: enabled_features(count_allocs),
+        }
+    }
+
+    /// A short, stable identifier of this fingerprint.
+    pub fn id(&self) -> String {
+        format!("{:08x}", fnv1a(format!("{self:?}").bytes()) >> 32)
+    }
+
+    /// Lists the fields that differ from `other`, e.g. `rustc: rustc 1.82.0 → rustc 1.83.0`.
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let mut diff = vec![];
+        let mut compare = |name: &str, a: String, b: String| {
+            if a != b {
+                diff.push(format!("{name}: {a} → {b}"));
+            }
+        };
+        compare("cpu", self.cpu_model.clone(), other.cpu_model.clone());
+        compare("cores", self.cores.to_string(), other.cores.to_string());
+        compare("rustc", self.rustc.clone(), other.rustc.clone());
+        compare("target", self.target.clone(), other.target.clone());
+        compare("profile", self.profile.clone(), other.profile.clone());
+        compare(
+            "features",
+            format!("{:?}", self.features),
+            format!("{:?}", other.features),
+        );
+        diff
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} cores), {}, {}, {}",
+            self.cpu_model, self.cores, self.rustc, self.target, self.profile
+        )?;
+        if !self.features.is_empty() {
+            write!(f, ", features: {}", self.features.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        matches!(key.trim(), "model name" | "Hardware" | "cpu model")
+            .then(|| value.trim().to_string())
+    })
+}
+
+/// Features of this crate that solutions are built with.
+/// Solutions are run through the same cargo alias as the CLI, so they share its features, but `count-allocs`
+/// is only added to the solutions' build with `--alloc`. Counting allocations slows them down, so it is part of
+/// the fingerprint.
+fn enabled_features(count_allocs: bool) -> Vec<String> {
+    let mut features = vec![];
+    if count_allocs || cfg!(feature = "count-allocs") {
+        features.push("count-allocs".to_string());
+    }
+    if cfg!(feature = "dhat-heap") {
+        features.push("dhat-heap".to_string());
+    }
+    if cfg!(feature = "today") {
+        features.push("today".to_string());
+    }
+    features
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{enabled_features, Fingerprint};
+
+    fn get_mock_fingerprint() -> Fingerprint {
+        Fingerprint {
+            cpu_model: "AMD Ryzen 7 5800X".into(),
+            cores: 16,
+            rustc: "rustc 1.83.0".into(),
+            target: "x86_64-unknown-linux-gnu".into(),
+            profile: "release".into(),
+            features: vec![],
+        }
+    }
+
+    #[test]
+    fn has_stable_ids() {
+        let fingerprint = get_mock_fingerprint();
+        assert_eq!(fingerprint.id(), get_mock_fingerprint().id());
+        assert_eq!(fingerprint.id().len(), 8);
+
+        let other = Fingerprint {
+            cores: 8,
+            ..get_mock_fingerprint()
+        };
+        assert_ne!(fingerprint.id(), other.id());
+    }
+
+    #[test]
+    fn diffs_fingerprints() {
+        let other = Fingerprint {
+            rustc: "rustc 1.84.0".into(),
+            ..get_mock_fingerprint()
+        };
+        assert_eq!(
+            get_mock_fingerprint().diff(&other),
+            vec!["rustc: rustc 1.83.0 → rustc 1.84.0".to_string()]
+        );
+    }
+
+    #[test]
+    fn includes_counted_allocations() {
+        assert!(enabled_features(true).contains(&"count-allocs".to_string()));
+    }
+}
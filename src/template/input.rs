@@ -0,0 +1,245 @@
+/// Loading of puzzle inputs and examples from the `data` directory.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{vault, Day};
+
+/// Error returned when an input file could not be read.
+#[derive(Debug)]
+pub struct ReadFileError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not read input file \"{}\": {}",
+            self.path.display(),
+            self.source
+        )
+    }
+}
+
+impl Error for ReadFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Options for reading an input file. By default, the file is read as-is.
+///
+/// ```
+/// # use advent_of_code::template::ReadOptions;
+/// let options = ReadOptions::new().part(2).normalized().trim_trailing_newline();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    part: Option<u8>,
+    strip_bom: bool,
+    crlf_to_lf: bool,
+    trim_trailing_newline: bool,
+}
+
+impl ReadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the file with a part suffix, e.g. `01-2.txt`.
+    #[must_use]
+    pub fn part(mut self, part: u8) -> Self {
+        self.part = Some(part);
+        self
+    }
+
+    /// Remove a leading UTF-8 byte order mark.
+    #[must_use]
+    pub fn strip_bom(mut self) -> Self {
+        self.strip_bom = true;
+        self
+    }
+
+    /// Convert `\r\n` line endings to `\n`.
+    #[must_use]
+    pub fn crlf_to_lf(mut self) -> Self {
+        self.crlf_to_lf = true;
+        self
+    }
+
+    /// Remove trailing line breaks.
+    #[must_use]
+    pub fn trim_trailing_newline(mut self) -> Self {
+        self.trim_trailing_newline = true;
+        self
+    }
+
+    /// Shorthand for [`ReadOptions::strip_bom`] and [`ReadOptions::crlf_to_lf`].
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        self.strip_bom().crlf_to_lf()
+    }
+
+    fn apply(&self, mut content: String) -> String {
+        if self.strip_bom {
+            if let Some(stripped) = content.strip_prefix('\u{feff}') {
+                content = stripped.to_string();
+            }
+        }
+        if self.crlf_to_lf && content.contains("\r\n") {
+            content = content.replace("\r\n", "\n");
+        }
+        if self.trim_trailing_newline {
+            let len = content.trim_end_matches(['\r', '\n']).len();
+            content.truncate(len);
+        }
+        content
+    }
+}
+
+/// Returns the `data` directory that inputs are read from.
+///
+/// Resolved from `AOC_DATA_DIR` if set, then from the crate's `CARGO_MANIFEST_DIR`, falling back to
+/// `data` in the current working directory.
+pub fn data_dir() -> PathBuf {
+    resolve_data_dir(
+        env::var_os("AOC_DATA_DIR").map(PathBuf::from),
+        env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .or_else(|| option_env!("CARGO_MANIFEST_DIR").map(PathBuf::from)),
+    )
+}
+
+fn resolve_data_dir(aoc_data_dir: Option<PathBuf>, manifest_dir: Option<PathBuf>) -> PathBuf {
+    if let Some(dir) = aoc_data_dir {
+        return dir;
+    }
+
+    if let Some(dir) = manifest_dir.map(|dir| dir.join("data")) {
+        if dir.is_dir() {
+            return dir;
+        }
+    }
+
+    // `data_dir` is only used for display purposes if the cwd is not accessible.
+    env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("data")
+}
+
+/// Returns the path of an input file, e.g. `data/inputs/01.txt` or `data/examples/01-2.txt`.
+pub fn input_path(folder: &str, day: Day, part: Option<u8>) -> PathBuf {
+    let file_name = match part {
+        Some(part) => format!("{day}-{part}.txt"),
+        None => format!("{day}.txt"),
+    };
+    data_dir().join(folder).join(file_name)
+}
+
+/// Reads a text file to a string as-is.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, ReadFileError> {
+    try_read_file_with(folder, day, ReadOptions::new())
+}
+
+/// Reads a text file to a string as-is, appending a part suffix. E.g. like `01-2.txt`.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, ReadFileError> {
+    try_read_file_with(folder, day, ReadOptions::new().part(part))
+}
+
+/// Reads a text file to a string, applying the given [`ReadOptions`].
+pub fn try_read_file_with(
+    folder: &str,
+    day: Day,
+    options: ReadOptions,
+) -> Result<String, ReadFileError> {
+    let path = input_path(folder, day, options.part);
+    read_path(&path).map(|content| options.apply(content))
+}
+
+/// Reads a file, transparently decrypting it from the vault if only its encrypted blob exists.
+fn read_path(path: &Path) -> Result<String, ReadFileError> {
+    let result = match fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound && vault::blob_path(path).exists() => {
+            vault::read_to_string(path).map_err(|e| match e {
+                vault::VaultError::IO(e) => e,
+                e => io::Error::other(e),
+            })
+        }
+        result => result,
+    };
+
+    result.map_err(|source| ReadFileError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Helper function that reads a text file to a string.
+///
+/// # Panics
+/// Panics with the path that was tried if the file can't be read, see [`try_read_file`].
+#[must_use]
+pub fn read_file(folder: &str, day: Day) -> String {
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// Panics with the path that was tried if the file can't be read, see [`try_read_file_part`].
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    try_read_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{resolve_data_dir, ReadOptions};
+
+    #[test]
+    fn reads_as_is_by_default() {
+        let s = "\u{feff}1\r\n2\r\n".to_string();
+        assert_eq!(ReadOptions::new().apply(s.clone()), s);
+    }
+
+    #[test]
+    fn normalizes_content() {
+        let s = "\u{feff}1\r\n2\r\n".to_string();
+        assert_eq!(ReadOptions::new().normalized().apply(s), "1\n2\n");
+    }
+
+    #[test]
+    fn trims_trailing_newlines() {
+        let s = "1\n2\r\n\n".to_string();
+        assert_eq!(ReadOptions::new().trim_trailing_newline().apply(s), "1\n2");
+    }
+
+    #[test]
+    fn prefers_aoc_data_dir() {
+        let dir = resolve_data_dir(
+            Some(PathBuf::from("/tmp/aoc")),
+            Some(PathBuf::from(env!("CARGO_MANIFEST_DIR"))),
+        );
+        assert_eq!(dir, PathBuf::from("/tmp/aoc"));
+    }
+
+    #[test]
+    fn uses_manifest_dir() {
+        let dir = resolve_data_dir(None, Some(PathBuf::from(env!("CARGO_MANIFEST_DIR"))));
+        assert_eq!(dir, PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data"));
+    }
+
+    #[test]
+    fn falls_back_to_cwd() {
+        let dir = resolve_data_dir(None, Some(PathBuf::from("/does/not/exist")));
+        assert_eq!(dir, std::env::current_dir().unwrap().join("data"));
+    }
+}
@@ -0,0 +1,154 @@
+/// Reduces noise in benchmarks by pinning the benchmark thread to a single core and raising its priority.
+/// Only supported on Linux, other platforms run without isolation.
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Nice values tried in order when raising the priority. Lowering the nice value requires
+/// `CAP_SYS_NICE` or a matching `RLIMIT_NICE`, so the first permitted one is used.
+#[cfg(target_os = "linux")]
+const NICE_LEVELS: [i32; 4] = [-20, -10, -5, -1];
+
+/// Governor that keeps the CPU at its highest frequency.
+pub const PERFORMANCE_GOVERNOR: &str = "performance";
+
+/// Settings a benchmark was run with.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Isolation {
+    /// Core the benchmark thread was pinned to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<usize>,
+    /// Nice value of the benchmark thread, if it could be raised.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice: Option<i32>,
+    /// CPU frequency governor of the core, if it could be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub governor: Option<String>,
+}
+
+impl Isolation {
+    /// Returns `false` if the frequency governor is known to scale the CPU frequency.
+    pub fn is_governor_ok(&self) -> bool {
+        self.governor
+            .as_deref()
+            .is_none_or(|governor| governor == PERFORMANCE_GOVERNOR)
+    }
+
+    /// Parses isolation settings printed with [`Display`], e.g. `cpu 3, nice -10, governor performance`.
+    pub fn parse(s: &str) -> Self {
+        let mut isolation = Isolation::default();
+
+        for field in s.split(", ") {
+            let Some((key, value)) = field.trim().split_once(' ') else {
+                continue;
+            };
+            match key {
+                "cpu" => isolation.cpu = value.parse().ok(),
+                "nice" => isolation.nice = value.parse().ok(),
+                "governor" => isolation.governor = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        isolation
+    }
+}
+
+impl Display for Isolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<String> = [
+            self.cpu.map(|cpu| format!("cpu {cpu}")),
+            self.nice.map(|nice| format!("nice {nice}")),
+            self.governor
+                .as_ref()
+                .map(|governor| format!("governor {governor}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if fields.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", fields.join(", "))
+        }
+    }
+}
+
+/// Pins the calling thread to the last core it may run on and raises its priority when permitted.
+/// The last core is picked as the system tends to schedule interrupts and other work on the first ones.
+#[cfg(target_os = "linux")]
+pub fn apply() -> Isolation {
+    let cpu = pin_to_last_cpu();
+    let nice = NICE_LEVELS
+        .into_iter()
+        // SAFETY: `setpriority` only reads its arguments, `0` targets the calling thread.
+        .find(|nice| unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, *nice) } == 0);
+    let governor = governor(cpu.unwrap_or(0));
+
+    Isolation {
+        cpu,
+        nice,
+        governor,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply() -> Isolation {
+    Isolation::default()
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_last_cpu() -> Option<usize> {
+    // SAFETY: `cpu_set_t` is a plain bitmask, all zeroes is a valid empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::cpu_set_t>();
+
+    // SAFETY: `set` is a valid `cpu_set_t` of `size` bytes, `0` targets the calling thread.
+    if unsafe { libc::sched_getaffinity(0, size, &mut set) } != 0 {
+        return None;
+    }
+
+    // SAFETY: `CPU_ISSET` only reads the set, the index is within `CPU_SETSIZE`.
+    let cpu = (0..libc::CPU_SETSIZE as usize)
+        .rev()
+        .find(|cpu| unsafe { libc::CPU_ISSET(*cpu, &set) })?;
+
+    // SAFETY: see above, the set is rebuilt to contain only `cpu`.
+    unsafe {
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(cpu, &mut set);
+        (libc::sched_setaffinity(0, size, &set) == 0).then_some(cpu)
+    }
+}
+
+/// Reads the frequency governor of a core from sysfs.
+#[cfg(target_os = "linux")]
+fn governor(cpu: usize) -> Option<String> {
+    std::fs::read_to_string(format!(
+        "/sys/devices/system/cpu/cpu{cpu}/cpufreq/scaling_governor"
+    ))
+    .ok()
+    .map(|governor| governor.trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Isolation;
+
+    #[test]
+    fn roundtrips_isolation() {
+        let isolation = Isolation {
+            cpu: Some(3),
+            nice: Some(-10),
+            governor: Some("powersave".into()),
+        };
+        assert_eq!(isolation.to_string(), "cpu 3, nice -10, governor powersave");
+        assert_eq!(Isolation::parse(&isolation.to_string()), isolation);
+        assert!(!isolation.is_governor_ok());
+
+        assert_eq!(Isolation::default().to_string(), "none");
+        assert_eq!(Isolation::parse("none"), Isolation::default());
+        assert!(Isolation::default().is_governor_ok());
+    }
+}
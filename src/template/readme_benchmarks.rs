@@ -1,11 +1,20 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs, io,
+    path::Path,
+    process::Command,
+    str::FromStr,
+};
 
-use crate::template::timings::Timings;
+use crate::template::run_multi::{get_path_for_bin, source_hash};
+use crate::template::timings::{format_nanos, PartStatus, PartTiming, Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -14,6 +23,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "{msg}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -21,22 +39,25 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Returns the marker of a table. Named tables use e.g. `<!--- benchmarking table:2015 --->`.
+fn marker(name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{MARKER_PREFIX}:{name} --->"),
+        None => MARKER.into(),
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -52,78 +73,485 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Checks that the README contains at least one benchmark table and that the markers of every table come in pairs.
+pub fn check_markers(readme: &str) -> Result<(), Error> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for (pos, _) in readme.match_indices(MARKER_PREFIX) {
+        let end = readme[pos..]
+            .find("--->")
+            .map(|i| pos + i + "--->".len())
+            .ok_or_else(|| Error::Parser(format!("found an unterminated marker at {pos}.")))?;
+        *counts.entry(&readme[pos..end]).or_default() += 1;
+    }
+
+    if counts.is_empty() {
+        return Err(Error::Parser(format!("could not find marker `{MARKER}`.")));
+    }
+
+    match counts.into_iter().find(|(_, n)| *n != 2) {
+        None => Ok(()),
+        Some((marker, 1)) => Err(Error::Parser(format!(
+            "found a single marker `{marker}`, expected an opening and a closing one."
+        ))),
+        Some((marker, n)) => Err(Error::Parser(format!(
+            "found {n} occurences of marker `{marker}`, expected exactly 2."
+        ))),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Optional columns of a benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Run time of parsing the input, for solutions that report it.
+    Parse,
+    /// Number of samples each part was benched with.
+    Samples,
+    /// Run time of the first, cold call of each part.
+    Cold,
+    /// Peak heap usage of each part.
+    Memory,
+    /// Number of heap allocations of each part.
+    Allocs,
+    /// Share of the day in the total run time, as a bar.
+    Share,
+    /// Change of each part's run time against the previously stored one.
+    Delta,
+}
+
+impl Column {
+    /// Per-part columns follow the column of their part, the others are rendered once per day.
+    fn is_per_part(self) -> bool {
+        matches!(
+            self,
+            Column::Samples | Column::Cold | Column::Memory | Column::Allocs | Column::Delta
+        )
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Samples => "Samples",
+            Column::Cold => "Cold",
+            Column::Memory => "Memory",
+            Column::Allocs => "Allocs",
+            Column::Share => "Share",
+            Column::Delta => "Δ",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "samples" => Ok(Column::Samples),
+            "cold" => Ok(Column::Cold),
+            "memory" => Ok(Column::Memory),
+            "allocs" => Ok(Column::Allocs),
+            "share" => Ok(Column::Share),
+            "delta" => Ok(Column::Delta),
+            _ => Err(format!(
+                "unknown column `{s}`, expected `parse`, `samples`, `cold`, `memory`, `allocs`, `share` or `delta`."
+            )),
+        }
+    }
+}
+
+/// Decides which table is updated and how it is rendered.
+#[derive(Clone, Debug, Default)]
+pub struct TableOptions {
+    /// Name of the table, `None` for the default table.
+    pub name: Option<String>,
+    /// Optional columns, in the order they are rendered in.
+    pub columns: Vec<Column>,
+}
+
+impl TableOptions {
+    /// Reads the optional columns from the comma-separated `AOC_BENCH_COLUMNS` variable.
+    pub fn from_env(name: Option<String>) -> Result<Self, String> {
+        let columns = match std::env::var("AOC_BENCH_COLUMNS") {
+            Ok(value) => value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+            Err(_) => vec![],
+        };
+        Ok(Self { name, columns })
+    }
+
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+/// Unix timestamps of the latest source change of each day's module.
+type SourceChanges = HashMap<Day, u64>;
+
+/// Returns when a day's module was last changed: its modification time if it has uncommitted changes,
+/// or the time of the last commit touching it otherwise.
+/// Committed files use the commit time, as checkouts reset modification times.
+fn last_source_change(day: Day) -> Option<u64> {
+    let path = get_path_for_bin(day);
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--", &path])
+        .output()
+        .map(|o| !o.status.success() || !o.stdout.is_empty())
+        .unwrap_or(true);
+
+    if !is_dirty {
+        let committed_at = Command::new("git")
+            .args(["log", "-1", "--format=%ct", "--", &path])
+            .output()
+            .ok()
+            .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok());
+
+        if committed_at.is_some() {
+            return committed_at;
+        }
+    }
+
+    fs::metadata(Path::new(&path))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+/// A part is stale if its day's module changed after it was recorded, i.e. the module's hash differs from the
+/// one stored with the part. Parts recorded before hashes were stored are stale if they were recorded before
+/// the latest change to the module.
+fn is_stale(day: Day, part: &PartTiming, context: &TableContext) -> bool {
+    if let Some(recorded_hash) = &part.source_hash {
+        return context
+            .source_hashes
+            .get(&day)
+            .is_some_and(|hash| hash != recorded_hash);
+    }
+
+    match (part.recorded_at, context.source_changes.get(&day)) {
+        (Some(recorded_at), Some(&changed_at)) => recorded_at < changed_at,
+        _ => false,
+    }
+}
+
+/// Labels for machines in the table, e.g. `A`, `B`. Empty if all parts were run on the same machine.
+fn machine_labels(timings: &Timings) -> HashMap<String, char> {
+    if timings.machines.len() < 2 {
+        return HashMap::new();
+    }
+    timings.machines.keys().cloned().zip('A'..='Z').collect()
+}
+
+fn format_part(
+    part: Option<&PartTiming>,
+    is_stale: bool,
+    labels: &HashMap<String, char>,
+) -> String {
+    let stale = if is_stale { "*" } else { "" };
+    let machine = part
+        .and_then(|p| labels.get(p.machine.as_ref()?))
+        .map(|label| format!("<sup>{label}</sup>"))
+        .unwrap_or_default();
+    let marker = format!("{stale}{machine}");
+    match part {
+        Some(PartTiming {
+            status: PartStatus::Solved,
+            nanos: Some(nanos),
+            ..
+        }) => format!("`{}`{marker}", format_nanos(*nanos)),
+        Some(PartTiming {
+            status: PartStatus::Failed,
+            ..
+        }) => "`✖`".into(),
+        _ => "`-`".into(),
+    }
+}
+
+/// Data besides the timings that a table is rendered from.
+#[derive(Default)]
+struct TableContext {
+    /// Timings stored before this run, to compute deltas against.
+    previous: Timings,
+    source_changes: SourceChanges,
+    /// Current hash of each day's module.
+    source_hashes: HashMap<Day, String>,
+}
+
+fn format_samples(part: Option<&PartTiming>) -> String {
+    match part.and_then(|p| p.samples) {
+        Some(samples) => format!("`{samples}`"),
+        None => "`-`".into(),
+    }
+}
+
+fn format_cold(part: Option<&PartTiming>) -> String {
+    match part.and_then(|p| p.cold_nanos) {
+        Some(nanos) => format!("`{}`", format_nanos(nanos)),
+        None => "`-`".into(),
+    }
+}
+
+fn format_allocs(part: Option<&PartTiming>) -> String {
+    match part.and_then(|p| p.allocations) {
+        Some(allocations) => format!("`{allocations}`"),
+        None => "`-`".into(),
+    }
+}
+
+fn format_memory(part: Option<&PartTiming>) -> String {
+    match part.and_then(|p| p.peak_bytes) {
+        Some(bytes) => format!("`{}`", format_bytes(bytes)),
+        None => "`-`".into(),
+    }
+}
+
+/// Formats a byte count with binary prefixes, e.g. `1.5 KiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Change of a part's run time against its previously stored run time, e.g. `-12.5%`.
+fn format_delta(part: Option<&PartTiming>, previous: Option<&PartTiming>) -> String {
+    match (part, previous) {
+        // parts that were not run this time did not change.
+        (Some(part), Some(previous)) if part == previous => "`-`".into(),
+        (Some(part), None) if part.is_solved() => "`new`".into(),
+        (Some(part), Some(previous)) => match (part.nanos, previous.nanos) {
+            (Some(nanos), Some(previous)) if previous > 0.0 => {
+                format!("`{:+.1}%`", (nanos - previous) / previous * 100.0)
+            }
+            _ => "`-`".into(),
+        },
+        _ => "`-`".into(),
+    }
+}
+
+/// Renders a share of the total run time as a bar of eighth blocks, e.g. `███▌      ` 35.0%.
+fn format_share(share: f64) -> String {
+    const WIDTH: usize = 10;
+    const BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let share = share.clamp(0.0, 1.0);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (share * (WIDTH * 8) as f64).round() as usize;
+
+    let mut bar = "█".repeat(eighths / 8);
+    if let Some(block) = (eighths % 8).checked_sub(1) {
+        bar.push(BLOCKS[block]);
+    }
+    format!("`{bar:<WIDTH$}` {:.1}%", share * 100.0)
+}
+
+fn construct_table(
+    prefix: &str,
+    options: &TableOptions,
+    timings: Timings,
+    total_millis: f64,
+    context: &TableContext,
+) -> String {
+    let marker = marker(options.name.as_deref());
+    let header = match &options.name {
+        Some(name) => format!("{prefix} Benchmarks ({name})"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    let per_part: Vec<Column> = options
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| c.is_per_part())
+        .collect();
+
+    let mut columns = vec!["Day".to_string()];
+    if options.has(Column::Parse) {
+        columns.push(Column::Parse.header().into());
+    }
+    for part in [1, 2] {
+        columns.push(format!("Part {part}"));
+        columns.extend(per_part.iter().map(|c| c.header().to_string()));
+    }
+    if options.has(Column::Share) {
+        columns.push(Column::Share.header().into());
+    }
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", columns.join(" | ")),
+        format!("| {}  |", vec![":---:"; columns.len()].join(" | ")),
     ];
 
-    for timing in timings.data {
+    let mut has_stale = false;
+    let labels = machine_labels(&timings);
+    let total_nanos: f64 = timings.data.iter().map(Timing::total_nanos).sum();
+
+    for timing in &timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let previous = context.previous.data.iter().find(|t| t.day == timing.day);
+
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+
+        if options.has(Column::Parse) {
+            cells.push(match timing.parse_nanos {
+                Some(nanos) => format!("`{}`", format_nanos(nanos)),
+                None => "`-`".into(),
+            });
+        }
+
+        for part in [1, 2] {
+            let timing_part = timing.part(part);
+            let stale = timing_part.is_some_and(|p| is_stale(timing.day, p, context));
+            has_stale |= stale;
+            cells.push(format_part(timing_part, stale, &labels));
+
+            for column in &per_part {
+                cells.push(match column {
+                    Column::Samples => format_samples(timing_part),
+                    Column::Cold => format_cold(timing_part),
+                    Column::Memory => format_memory(timing_part),
+                    Column::Allocs => format_allocs(timing_part),
+                    _ => format_delta(timing_part, previous.and_then(|t| t.part(part))),
+                });
+            }
+        }
+
+        if options.has(Column::Share) {
+            cells.push(if total_nanos > 0.0 {
+                format_share(timing.total_nanos() / total_nanos)
+            } else {
+                "`-`".into()
+            });
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if has_stale {
+        lines.push(String::new());
+        lines.push("\\* stale: the solution changed after this part was benched.".into());
+    }
+
+    if labels.is_empty() {
+        if let Some(fingerprint) = timings.machines.values().next() {
+            lines.push(String::new());
+            lines.push(format!("_Benchmarked on {fingerprint}._"));
+        }
+    } else {
+        lines.push(String::new());
+        lines.push("Benchmarked on:".into());
+        lines.push(String::new());
+        for (id, fingerprint) in &timings.machines {
+            lines.push(format!("- <sup>{}</sup> {fingerprint}", labels[id]));
+        }
+    }
+
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    options: &TableOptions,
+    timings: Timings,
+    total_millis: f64,
+    context: &TableContext,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &marker(options.name.as_deref()))?;
+    let table = construct_table("##", options, timings, total_millis, context);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Updates the table selected by `options` in the README. `previous` are the timings stored before this run.
+pub fn update(timings: Timings, previous: Timings, options: &TableOptions) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let source_changes = timings
+        .data
+        .iter()
+        .filter_map(|t| Some((t.day, last_source_change(t.day)?)))
+        .collect();
+    let source_hashes = timings
+        .data
+        .iter()
+        .filter_map(|t| Some((t.day, source_hash(t.day)?)))
+        .collect();
+    let context = TableContext {
+        previous,
+        source_changes,
+        source_hashes,
+    };
+    update_content(&mut readme, options, timings, total_millis, &context)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::collections::HashMap;
+
+    use super::{
+        check_markers, format_bytes, format_share, update_content, Column, TableContext,
+        TableOptions, MARKER,
+    };
+    use crate::{
+        day,
+        template::{
+            fingerprint::Fingerprint,
+            timings::{MergePolicy, PartTiming, Timing, Timings},
+        },
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(PartTiming::solved(1e+7, Some(10))),
+                    part_2: Some(PartTiming::solved(2e+7, Some(10))),
+                    parse_nanos: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(PartTiming::solved(3e+7, Some(10))),
+                    part_2: Some(PartTiming::solved(4e+7, Some(10))),
+                    parse_nanos: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(PartTiming::solved(4e+7, Some(10))),
+                    part_2: Some(PartTiming::solved(5e+7, Some(10))),
+                    parse_nanos: None,
                 },
             ],
+            ..Default::default()
         }
     }
 
@@ -131,28 +559,71 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            get_mock_timings(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            get_mock_timings(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn checks_marker_balance() {
+        assert!(check_markers("# readme").is_err());
+        assert!(check_markers(MARKER).is_err());
+        assert!(check_markers(&format!("{MARKER}\n{MARKER}")).is_ok());
+        assert!(check_markers(&format!("{MARKER}{MARKER}{MARKER}")).is_err());
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            get_mock_timings(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            get_mock_timings(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            get_mock_timings(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +631,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            get_mock_timings(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +647,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +658,162 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn marks_stale_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().recorded_at = Some(100);
+        timings.data[0].part_2.as_mut().unwrap().recorded_at = Some(300);
+        let context = TableContext {
+            source_changes: HashMap::from([(day!(1), 200)]),
+            ..TableContext::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &TableOptions::default(), timings, 190.0, &context).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms`* | `20.0ms` |"));
+        assert!(s.contains("\\* stale"));
+    }
+
+    #[test]
+    fn compares_source_hashes() {
+        // committed after benching: the commit is newer than the timing, but the module did not change.
+        let mut timings = get_mock_timings();
+        let day_1 = &mut timings.data[0];
+        for (part, hash) in [(&mut day_1.part_1, "abc"), (&mut day_1.part_2, "def")] {
+            let part = part.as_mut().unwrap();
+            part.recorded_at = Some(100);
+            part.source_hash = Some(hash.into());
+        }
+        let context = TableContext {
+            source_changes: HashMap::from([(day!(1), 200)]),
+            source_hashes: HashMap::from([(day!(1), "abc".to_string())]),
+            ..TableContext::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &TableOptions::default(), timings, 190.0, &context).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms`* |"));
+    }
+
+    #[test]
+    fn shows_machines() {
+        let fingerprint = Fingerprint {
+            cpu_model: "AMD Ryzen 7 5800X".into(),
+            cores: 16,
+            rustc: "rustc 1.83.0".into(),
+            target: "x86_64-unknown-linux-gnu".into(),
+            profile: "release".into(),
+            features: vec![],
+        };
+        let mut timings = get_mock_timings();
+        timings.tag_machine(&fingerprint);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            timings.clone(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
+        assert!(s.contains("_Benchmarked on AMD Ryzen 7 5800X (16 cores), rustc 1.83.0, x86_64-unknown-linux-gnu, release._"));
+
+        let mut other = Timings {
+            data: vec![timings.data[0].clone()],
+            ..Timings::default()
+        };
+        other.tag_machine(&Fingerprint {
+            cores: 8,
+            ..fingerprint
+        });
+        let merged = timings.merge(&other, MergePolicy::Latest);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &TableOptions::default(),
+            merged,
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
+        assert!(s.contains("Benchmarked on:"));
+        assert!(s.contains("`10.0ms`<sup>"));
+    }
+
+    #[test]
+    fn updates_named_tables() {
+        let named = "<!--- benchmarking table:2015 --->";
+        let mut s = format!("{MARKER}\n{MARKER}\n{named}\n{named}");
+        let options = TableOptions {
+            name: Some("2015".into()),
+            ..TableOptions::default()
+        };
+        update_content(
+            &mut s,
+            &options,
+            get_mock_timings(),
+            190.0,
+            &TableContext::default(),
+        )
+        .unwrap();
+
+        assert!(s.starts_with(&format!(
+            "{MARKER}\n{MARKER}\n{named}\n## Benchmarks (2015)"
+        )));
+        assert!(s.ends_with(named));
+        assert!(check_markers(&s).is_ok());
+        assert!(check_markers(&format!("{MARKER}\n{MARKER}\n{named}")).is_err());
+    }
+
+    #[test]
+    fn renders_optional_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().peak_bytes = Some(2048);
+        timings.data[0].part_1.as_mut().unwrap().allocations = Some(12);
+        timings.data[0].parse_nanos = Some(5e+6);
+
+        let mut previous = get_mock_timings();
+        previous.data[0].part_1.as_mut().unwrap().nanos = Some(2e+7);
+        previous.data.remove(2);
+
+        let options = TableOptions {
+            name: None,
+            columns: vec![
+                Column::Parse,
+                Column::Samples,
+                Column::Memory,
+                Column::Allocs,
+                Column::Delta,
+                Column::Share,
+            ],
+        };
+        let context = TableContext {
+            previous,
+            ..TableContext::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &options, timings, 190.0, &context).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Samples | Memory | Allocs | Δ | Part 2 | Samples | Memory | Allocs | Δ | Share |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `5.0ms` | `10.0ms` | `10` | `2.0 KiB` | `12` | `-50.0%` | `20.0ms` | `10` | `-` | `-` | `-` | `█▋        ` 15.8% |"));
+        assert!(
+            s.contains("| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `10` | `-` | `-` | `new` |")
+        );
+    }
+
+    #[test]
+    fn formats_helpers() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(format_share(0.0), "`          ` 0.0%");
+        assert_eq!(format_share(0.35), "`███▌      ` 35.0%");
+        assert_eq!(format_share(1.0), "`██████████` 100.0%");
+    }
 }
@@ -0,0 +1,221 @@
+/// Module that updates the readme with star progress and writes progress badges.
+/// Stars are computed from the local ledger of accepted answers, see [`Answers`].
+use std::{fs, path::Path};
+
+use crate::template::answers::{Answers, MAX_STARS};
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::{all_days, Day};
+
+static MARKER: &str = "<!--- local stars table --->";
+
+static BADGES_DIR: &str = "./.assets";
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Weekday of the 1st of December of `year`, counting from Monday as `0`.
+fn first_weekday(year: u16) -> usize {
+    // Sakamoto's method, which counts from Sunday as `0`.
+    let y = usize::from(year);
+    let sunday_based = (y + y / 4 - y / 100 + y / 400 + 4 + 1) % 7;
+    (sunday_based + 6) % 7
+}
+
+fn format_day(day: Day, year: Option<u16>, answers: &Answers) -> String {
+    let stars = "⭐".repeat(answers.stars(day).into());
+    let n = day.into_inner();
+    let link = match year {
+        Some(year) => format!("[{n}](https://adventofcode.com/{year}/day/{n})"),
+        None => n.to_string(),
+    };
+    if stars.is_empty() {
+        link
+    } else {
+        format!("{link} {stars}")
+    }
+}
+
+/// Renders a calendar of December with the stars collected on each day.
+/// Without a year, the calendar starts on a Monday.
+fn construct_table(prefix: &str, year: Option<u16>, answers: &Answers) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("{prefix} ⭐ Progress"),
+        String::new(),
+        format!("| {} |", WEEKDAYS.join(" | ")),
+        format!("| {} |", [":---:"; 7].join(" | ")),
+    ];
+
+    let offset = year.map_or(0, first_weekday);
+    let mut cells: Vec<String> = vec![String::new(); offset];
+    cells.extend(all_days().map(|day| format_day(day, year, answers)));
+
+    for week in cells.chunks(7) {
+        let mut week = week.to_vec();
+        week.resize(WEEKDAYS.len(), String::new());
+        let row: String = week
+            .iter()
+            .map(|cell| {
+                if cell.is_empty() {
+                    " |".to_string()
+                } else {
+                    format!(" {cell} |")
+                }
+            })
+            .collect();
+        lines.push(format!("|{row}"));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Stars: {} / {MAX_STARS}**",
+        answers.total_stars()
+    ));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: Option<u16>, answers: &Answers) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, answers);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: Option<u16>, answers: &Answers) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, answers)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders a flat badge in the style of shields.io.
+fn badge(label: &str, message: &str, color: &str) -> String {
+    // approximate width of a character in 11px Verdana, plus padding.
+    let width = |s: &str| s.chars().count() * 7 + 10;
+    let (label_width, message_width) = (width(label), width(message));
+    let total_width = label_width + message_width;
+    let (label_x, message_x) = (label_width / 2, label_width + message_width / 2);
+    let (label, message) = (escape_xml(label), escape_xml(message));
+
+    [
+        format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{total_width}" height="20" role="img" aria-label="{label}: {message}">"#),
+        format!("  <title>{label}: {message}</title>"),
+        format!(r##"  <rect width="{label_width}" height="20" fill="#555"/>"##),
+        format!(r#"  <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>"#),
+        r##"  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">"##.into(),
+        format!(r#"    <text x="{label_x}" y="14">{label}</text>"#),
+        format!(r#"    <text x="{message_x}" y="14">{message}</text>"#),
+        "  </g>".into(),
+        "</svg>".into(),
+    ]
+    .join("\n")
+        + "\n"
+}
+
+fn stars_badge(stars: u8) -> String {
+    let color = match stars {
+        0 => "#9f9f9f",
+        MAX_STARS => "#4c1",
+        _ => "#dfb317",
+    };
+    badge("stars", &format!("{stars}/{MAX_STARS}"), color)
+}
+
+fn runtime_badge(total_millis: f64) -> String {
+    badge("runtime", &format!("{total_millis:.2}ms"), "#007ec6")
+}
+
+/// Writes the `stars.svg` badge and, if any timings are stored, the `runtime.svg` badge.
+/// Returns the paths of the written badges.
+pub fn write_badges(stars: u8, total_millis: Option<f64>) -> Result<Vec<String>, Error> {
+    fs::create_dir_all(Path::new(BADGES_DIR))?;
+
+    let mut badges = vec![(format!("{BADGES_DIR}/stars.svg"), stars_badge(stars))];
+    if let Some(total_millis) = total_millis {
+        badges.push((
+            format!("{BADGES_DIR}/runtime.svg"),
+            runtime_badge(total_millis),
+        ));
+    }
+
+    for (path, svg) in &badges {
+        fs::write(path, svg)?;
+    }
+
+    Ok(badges.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{first_weekday, runtime_badge, stars_badge, update_content, MARKER};
+    use crate::{day, template::answers::Answers};
+
+    #[test]
+    fn computes_first_weekday() {
+        // 2015-12-01 was a tuesday, 2023-12-01 a friday and 2024-12-01 a sunday.
+        assert_eq!(first_weekday(2015), 1);
+        assert_eq!(first_weekday(2023), 4);
+        assert_eq!(first_weekday(2024), 6);
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "1");
+        answers.record(day!(1), 2, "2");
+        answers.record(day!(2), 1, "3");
+
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbar");
+        update_content(&mut s, Some(2015), &answers).unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            "## ⭐ Progress",
+            "",
+            "| Mon | Tue | Wed | Thu | Fri | Sat | Sun |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| | [1](https://adventofcode.com/2015/day/1) ⭐⭐ | [2](https://adventofcode.com/2015/day/2) ⭐ | [3](https://adventofcode.com/2015/day/3) | [4](https://adventofcode.com/2015/day/4) | [5](https://adventofcode.com/2015/day/5) | [6](https://adventofcode.com/2015/day/6) |",
+        ]
+        .join("\n");
+        assert!(s.starts_with(&expected));
+        assert!(s.contains("| [21](https://adventofcode.com/2015/day/21) | [22](https://adventofcode.com/2015/day/22) | [23](https://adventofcode.com/2015/day/23) | [24](https://adventofcode.com/2015/day/24) | [25](https://adventofcode.com/2015/day/25) | | |"));
+        assert!(s.ends_with(&format!("**Stars: 3 / 50**\n{MARKER}\nbar")));
+    }
+
+    #[test]
+    fn renders_calendar_without_year() {
+        let mut s = format!("{MARKER}\n{MARKER}");
+        update_content(&mut s, None, &Answers::default()).unwrap();
+        assert!(s.contains("| 1 | 2 | 3 | 4 | 5 | 6 | 7 |"));
+        assert!(s.contains("| 22 | 23 | 24 | 25 | | | |"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, None, &Answers::default()).unwrap();
+    }
+
+    #[test]
+    fn renders_badges() {
+        let badge = stars_badge(12);
+        assert!(badge.starts_with("<svg"));
+        assert!(badge.contains(">12/50</text>"));
+        assert!(badge.contains("#dfb317"));
+        assert!(stars_badge(50).contains("#4c1"));
+        assert!(runtime_badge(190.0).contains(">190.00ms</text>"));
+    }
+}
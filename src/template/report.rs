@@ -0,0 +1,376 @@
+/// Module that renders stored timings and accepted answers as a report to share outside of the readme.
+use std::{collections::BTreeSet, fmt::Write, str::FromStr};
+
+use crate::template::answers::Answers;
+use crate::template::timings::{format_nanos, PartStatus, PartTiming, Timings};
+use crate::template::Day;
+
+/// Output format of a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "unknown report format `{s}`, expected `csv`, `md` or `html`."
+            )),
+        }
+    }
+}
+
+/// A single part of a day, as it appears in a report.
+struct Row<'a> {
+    day: Day,
+    part: u8,
+    timing: Option<&'a PartTiming>,
+    answer: Option<&'a str>,
+}
+
+impl Row<'_> {
+    fn status(&self) -> &'static str {
+        match self.timing.map(|t| t.status) {
+            Some(PartStatus::Solved) => "solved",
+            Some(PartStatus::Unsolved) => "unsolved",
+            Some(PartStatus::Failed) => "failed",
+            None => "not run",
+        }
+    }
+
+    fn nanos(&self) -> Option<f64> {
+        self.timing.and_then(|t| t.nanos)
+    }
+
+    fn samples(&self) -> Option<u64> {
+        self.timing.and_then(|t| t.samples)
+    }
+
+    fn is_verified(&self) -> bool {
+        self.answer.is_some()
+    }
+}
+
+/// Rows of all parts that were benched or have an accepted answer, ordered by day and part.
+fn rows<'a>(timings: &'a Timings, answers: &'a Answers) -> Vec<Row<'a>> {
+    let days: BTreeSet<Day> = timings
+        .data
+        .iter()
+        .map(|t| t.day)
+        .chain(answers.data.iter().map(|a| a.day))
+        .collect();
+
+    days.into_iter()
+        .flat_map(|day| {
+            let timing = timings.data.iter().find(|t| t.day == day);
+            [1, 2].map(|part| Row {
+                day,
+                part,
+                timing: timing.and_then(|t| t.part(part)),
+                answer: answers.get(day, part),
+            })
+        })
+        .collect()
+}
+
+pub fn render(format: Format, timings: &Timings, answers: &Answers) -> String {
+    let rows = rows(timings, answers);
+    match format {
+        Format::Csv => render_csv(&rows),
+        Format::Markdown => render_markdown(&rows, timings),
+        Format::Html => render_html(&rows, timings),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn render_csv(rows: &[Row]) -> String {
+    let mut out = String::from(
+        "day,part,status,nanos,cold_nanos,samples,allocations,alloc_bytes,peak_bytes,verified,answer\n",
+    );
+
+    for row in rows {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            row.day.into_inner(),
+            row.part,
+            row.status(),
+            optional(row.nanos().map(|n| n.to_string())),
+            optional(row.timing.and_then(|t| t.cold_nanos).map(|n| n.to_string())),
+            optional(row.samples().map(|n| n.to_string())),
+            optional(
+                row.timing
+                    .and_then(|t| t.allocations)
+                    .map(|n| n.to_string())
+            ),
+            optional(
+                row.timing
+                    .and_then(|t| t.alloc_bytes)
+                    .map(|n| n.to_string())
+            ),
+            optional(row.timing.and_then(|t| t.peak_bytes).map(|n| n.to_string())),
+            row.is_verified(),
+            escape_csv(row.answer.unwrap_or_default()),
+        );
+    }
+
+    out
+}
+
+fn format_time(row: &Row) -> String {
+    row.nanos().map(format_nanos).unwrap_or_else(|| "-".into())
+}
+
+fn render_markdown(rows: &[Row], timings: &Timings) -> String {
+    let mut lines = vec![
+        "| Day | Part | Status | Time | Samples | Verified |".to_string(),
+        "| :---: | :---: | :---: | ---: | ---: | :---: |".to_string(),
+    ];
+
+    for row in rows {
+        lines.push(format!(
+            "| {} | {} | {} | `{}` | {} | {} |",
+            row.day.into_inner(),
+            row.part,
+            row.status(),
+            format_time(row),
+            row.samples().map_or_else(|| "-".into(), |n| n.to_string()),
+            if row.is_verified() { "✔" } else { "" },
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Horizontal bar chart of the run time of each day, scaled to the slowest day.
+fn runtime_chart(timings: &Timings) -> String {
+    const BAR_WIDTH: f64 = 400.0;
+    const ROW_HEIGHT: usize = 20;
+    const LABEL_WIDTH: usize = 60;
+
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos())
+        .fold(0.0, f64::max);
+
+    let height = timings.data.len() * ROW_HEIGHT;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{height}" role="img" aria-label="Run time per day">"#,
+        LABEL_WIDTH + 500,
+    );
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        let nanos = timing.total_nanos();
+        let width = if max_nanos > 0.0 {
+            nanos / max_nanos * BAR_WIDTH
+        } else {
+            0.0
+        };
+        let y = i * ROW_HEIGHT;
+        let _ = write!(
+            svg,
+            r##"<text x="0" y="{}" font-size="12">Day {}</text><rect x="{LABEL_WIDTH}" y="{}" width="{width:.1}" height="{}" fill="#007ec6"><title>{}</title></rect><text x="{:.1}" y="{}" font-size="12">{}</text>"##,
+            y + 14,
+            timing.day.into_inner(),
+            y + 3,
+            ROW_HEIGHT - 6,
+            format_nanos(nanos),
+            LABEL_WIDTH as f64 + width + 6.0,
+            y + 14,
+            format_nanos(nanos),
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+static HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: right; }
+th { cursor: pointer; user-select: none; }
+th:hover { background: #f3f3f3; }
+.failed { color: #c00; }";
+
+/// Sorts the table by the clicked column, using the `data-sort` value of cells if present.
+static HTML_SCRIPT: &str = "document.querySelectorAll('th').forEach((th, column) => {
+  let ascending = true;
+  th.addEventListener('click', () => {
+    const body = th.closest('table').tBodies[0];
+    const value = (row) => {
+      const cell = row.cells[column];
+      const raw = cell.dataset.sort ?? cell.textContent;
+      const number = parseFloat(raw);
+      return isNaN(number) ? raw : number;
+    };
+    const rows = [...body.rows].sort((a, b) => {
+      const [x, y] = [value(a), value(b)];
+      return (x < y ? -1 : x > y ? 1 : 0) * (ascending ? 1 : -1);
+    });
+    ascending = !ascending;
+    rows.forEach((row) => body.appendChild(row));
+  });
+});";
+
+fn render_html(rows: &[Row], timings: &Timings) -> String {
+    let mut body = String::new();
+
+    for row in rows {
+        let _ = writeln!(
+            body,
+            r#"<tr class="{}"><td>{}</td><td>{}</td><td>{}</td><td data-sort="{}">{}</td><td>{}</td><td data-sort="{}">{}</td></tr>"#,
+            row.status(),
+            row.day.into_inner(),
+            row.part,
+            row.status(),
+            row.nanos().map_or(-1.0, |n| n),
+            format_time(row),
+            row.samples().map_or_else(|| "-".into(), |n| n.to_string()),
+            u8::from(row.is_verified()),
+            if row.is_verified() { "✔" } else { "" },
+        );
+    }
+
+    let chart = if timings.data.is_empty() {
+        String::new()
+    } else {
+        format!("<h2>Run time per day</h2>\n{}\n", runtime_chart(timings))
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code report</title>
+<style>
+{HTML_STYLE}
+</style>
+</head>
+<body>
+<h1>Advent of Code report</h1>
+<p>Total: {total:.2}ms</p>
+<table>
+<thead><tr><th>Day</th><th>Part</th><th>Status</th><th>Time</th><th>Samples</th><th>Verified</th></tr></thead>
+<tbody>
+{body}</tbody>
+</table>
+{chart}<script>
+{HTML_SCRIPT}
+</script>
+</body>
+</html>
+"#,
+        total = timings.total_millis(),
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{escape_csv, render, Format};
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+
+    fn get_mock_data() -> (Timings, Answers) {
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming::solved(1e+7, Some(10))),
+                    part_2: Some(PartTiming::failed()),
+                    parse_nanos: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some(PartTiming::solved(3e+7, Some(10))),
+                    part_2: None,
+                    parse_nanos: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "42");
+        answers.record(day!(3), 1, "a, \"b\"");
+        (timings, answers)
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse::<Format>(), Ok(Format::Csv));
+        assert_eq!("md".parse::<Format>(), Ok(Format::Markdown));
+        assert_eq!("html".parse::<Format>(), Ok(Format::Html));
+        assert!("pdf".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let (timings, answers) = get_mock_data();
+        let csv = render(Format::Csv, &timings, &answers);
+        let expected = [
+            "day,part,status,nanos,cold_nanos,samples,allocations,alloc_bytes,peak_bytes,verified,answer",
+            "1,1,solved,10000000,,10,,,,true,42",
+            "1,2,failed,,,,,,,false,",
+            "2,1,solved,30000000,,10,,,,false,",
+            "2,2,not run,,,,,,,false,",
+            "3,1,not run,,,,,,,true,\"a, \"\"b\"\"\"",
+            "3,2,not run,,,,,,,false,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+        assert_eq!(escape_csv("plain"), "plain");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let (timings, answers) = get_mock_data();
+        let md = render(Format::Markdown, &timings, &answers);
+        assert!(md.contains("| 1 | 1 | solved | `10.0ms` | 10 | ✔ |"));
+        assert!(md.contains("| 1 | 2 | failed | `-` | - |  |"));
+        assert!(md.ends_with("**Total: 40.00ms**\n"));
+    }
+
+    #[test]
+    fn renders_html() {
+        let (timings, answers) = get_mock_data();
+        let html = render(Format::Html, &timings, &answers);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(r#"<td data-sort="10000000">10.0ms</td>"#));
+        assert!(html.contains("<svg"));
+        assert!(html.contains("<title>30.0ms</title>"));
+        assert!(html.contains("addEventListener"));
+        // the report is self-contained.
+        assert!(!html.contains("src="));
+        assert!(!html.contains("href="));
+    }
+}
@@ -1,13 +1,20 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fs, io};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    fingerprint::fnv1a,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    count_allocs: bool,
+    isolate: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,18 +31,28 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            // hashed before running, as the solution is built from the module as it is now.
+            let source = source_hash(day);
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, count_allocs, isolate)
+                    .unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let mut val = child_commands::parse_exec_time(&output, day);
+                for part in [&mut val.part_1, &mut val.part_2].into_iter().flatten() {
+                    part.source_hash.clone_from(&source);
+                }
                 timings.push(val);
             }
         });
 
     if is_timed {
-        let timings = Timings { data: timings };
+        let timings = Timings {
+            data: timings,
+            ..Timings::default()
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -64,10 +81,20 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// Hash of the content of a day's module, to tell whether it changed since a part was benched.
+pub fn source_hash(day: Day) -> Option<String> {
+    fs::read(get_path_for_bin(day))
+        .ok()
+        .map(|content| format!("{:016x}", fnv1a(content)))
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::alloc::{self, AllocStats};
+    use crate::template::isolate::Isolation;
+    use crate::template::timings::{parse_duration, PartTiming, Timing};
     use crate::template::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -77,7 +104,13 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        count_allocs: bool,
+        isolate: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -90,10 +123,18 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        if count_allocs {
+            args.extend(["--features", "count-allocs"]);
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
             args.push("--time");
+
+            if isolate {
+                args.push("--isolate");
+            }
         }
 
         // spawn child command with piped stdout/stderr.
@@ -128,65 +169,96 @@ pub mod child_commands {
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    pub fn parse_exec_time(output: &[String], day: Day) -> Timing {
+        let mut timing = Timing::new(day);
+        let mut isolation = None;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        for line in output {
+            // intermediate results are overwritten with a carriage return, check the final one.
+            let line = line.rsplit('\r').next().unwrap_or_default();
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            if line.starts_with("Parse: ") {
+                timing.parse_nanos = parse_time(line).map(|(nanos, _)| nanos);
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            if let Some(settings) = line.strip_prefix("Isolation: ") {
+                isolation = Some(Isolation::parse(settings));
+                continue;
+            }
 
-        timings
-    }
+            let Some((part, rest)) = line
+                .strip_prefix("Part ")
+                .and_then(|rest| rest.split_once(": "))
+            else {
+                continue;
+            };
+
+            let Some(slot) = part.parse::<u8>().ok().and_then(|p| timing.part_mut(p)) else {
+                continue;
+            };
+
+            if let Some(message) = rest.strip_prefix('✖') {
+                let part = if message.trim().is_empty() {
+                    PartTiming::unsolved()
+                } else {
+                    PartTiming::failed()
+                };
+                *slot = Some(part.recorded_now());
+            } else if line.contains(" samples)") || line.contains(" samples, ") {
+                match parse_time(line) {
+                    Some((nanos, samples)) => {
+                        let mut part = PartTiming::solved(nanos, Some(samples)).recorded_now();
+                        part.cold_nanos = parse_cold(line);
+                        part.isolation.clone_from(&isolation);
+                        if let Some(stats) = parse_allocs(line) {
+                            part = part.with_allocs(stats);
+                        }
+                        *slot = Some(part);
+                    }
+                    None => eprintln!("Could not parse timings from line: {line}"),
+                }
+            }
+        }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+        timing
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
+    /// Parses the average duration in nanoseconds and the sample count from a line like `Part 1: 42 (1.2ms @ 10 samples)`.
+    fn parse_time(line: &str) -> Option<(f64, u64)> {
+        let (str_timing, str_samples) = line
+            .split(" samples")
             .next()?
             .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+            .next_back()?
+            .split_once('@')?;
+
+        Some((
+            parse_duration(str_timing.trim())?,
+            str_samples.trim().parse().ok()?,
+        ))
+    }
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+    /// Returns the fields that follow the timing in a line like `Part 1: 42 (1.2ms @ 10 samples, cold 2.1ms, 3 allocs)`.
+    fn extra_fields(line: &str) -> impl Iterator<Item = &str> {
+        line.strip_suffix(')')
+            .and_then(|line| line.rsplit_once('('))
+            .map(|(_, fields)| fields)
+            .unwrap_or_default()
+            .split(", ")
+            .skip(1)
+    }
+
+    /// Parses the run time of the first, cold call in nanoseconds.
+    fn parse_cold(line: &str) -> Option<f64> {
+        extra_fields(line)
+            .find_map(|field| field.strip_prefix("cold "))
+            .and_then(parse_duration)
+    }
 
-        Some((str_timing, parsed_timing))
+    /// Parses the heap usage, printed like `3 allocs, 96 B, peak 64 B`.
+    fn parse_allocs(line: &str) -> Option<AllocStats> {
+        alloc::parse_stats(extra_fields(line))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -207,7 +279,7 @@ pub mod child_commands {
     mod tests {
         use super::parse_exec_time;
 
-        use crate::day;
+        use crate::{day, template::timings::PartStatus};
 
         #[test]
         fn parses_execution_times() {
@@ -219,9 +291,11 @@ pub mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos(), 74130074.13_f64);
+            assert_approx_eq!(res.part_1.as_ref().unwrap().nanos.unwrap(), 74.13_f64);
+            assert_eq!(res.part_1.unwrap().samples, Some(100000));
+            assert_approx_eq!(res.part_2.as_ref().unwrap().nanos.unwrap(), 74130000_f64);
+            assert_eq!(res.part_2.unwrap().samples, Some(99999));
         }
 
         #[test]
@@ -234,9 +308,9 @@ pub mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos(), 2100000000_f64);
+            assert_eq!(res.part_1.unwrap().samples, Some(5));
+            assert_eq!(res.part_2.unwrap().samples, Some(1));
         }
 
         #[test]
@@ -249,9 +323,75 @@ pub mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_approx_eq!(res.total_nanos(), 0_f64);
+            assert_eq!(res.part_1.unwrap().status, PartStatus::Unsolved);
+            assert_eq!(res.part_2.unwrap().status, PartStatus::Unsolved);
+        }
+
+        #[test]
+        fn parses_failed_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖\rPart 2: ✖ \x1b[1merror:\x1b[0m bad input (line 3)".into(),
+                    "  caused by: invalid digit found in string".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.as_ref().unwrap().status, PartStatus::Solved);
+            assert_eq!(res.part_2.as_ref().unwrap().status, PartStatus::Failed);
+            assert!(res.part_1.unwrap().recorded_at.is_some());
+            assert!(res.part_2.unwrap().recorded_at.is_some());
+        }
+
+        #[test]
+        fn parses_parse_time() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.5µs @ 1000 samples)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.parse_nanos.unwrap(), 1500_f64);
+            assert_approx_eq!(res.total_nanos(), 74.13_f64);
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples, cold 2.5µs, 3 allocs, 96 B, peak 64 B)"
+                        .into(),
+                    "Part 2: (1) (2s @ 5 samples)".into(),
+                ],
+                day!(1),
+            );
+            let part_1 = res.part_1.unwrap();
+            assert_approx_eq!(part_1.nanos.unwrap(), 74.13_f64);
+            assert_eq!(part_1.samples, Some(100000));
+            assert_eq!(part_1.allocations, Some(3));
+            assert_eq!(part_1.alloc_bytes, Some(96));
+            assert_eq!(part_1.peak_bytes, Some(64));
+            assert_approx_eq!(part_1.cold_nanos.unwrap(), 2500_f64);
+            assert_eq!(res.part_2.unwrap().allocations, None);
+        }
+
+        #[test]
+        fn parses_isolation() {
+            let res = parse_exec_time(
+                &[
+                    "Isolation: cpu 7, governor powersave".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖        ".into(),
+                ],
+                day!(1),
+            );
+            let isolation = res.part_1.unwrap().isolation.unwrap();
+            assert_eq!(isolation.cpu, Some(7));
+            assert_eq!(isolation.nice, None);
+            assert_eq!(isolation.governor.as_deref(), Some("powersave"));
+            assert_eq!(res.part_2.unwrap().isolation, None);
         }
     }
 }
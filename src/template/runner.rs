@@ -1,104 +1,425 @@
 /// Encapsulates code that interacts with solution functions.
+use std::convert::Infallible;
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::search;
+use crate::template::alloc::AllocStats;
+use crate::template::answers::Answers;
+use crate::template::isolate::{self, PERFORMANCE_GOVERNOR};
+use crate::template::scaling::{self, Unit, Variant};
+use crate::template::timings::format_nanos;
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Return types accepted from solution functions.
+///
+/// Solutions can return an [`Option`] if there is no more to say about a missing answer,
+/// or a [`Result`] if they want to explain what went wrong, e.g. on malformed input.
+pub trait SolutionOutput {
+    type Answer: Display;
+
+    fn into_answer(self) -> Result<Self::Answer, SolutionError>;
+}
+
+impl<T: Display> SolutionOutput for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, SolutionError> {
+        self.ok_or(SolutionError::NoAnswer)
+    }
+}
+
+/// Errors have to convert into a `Box<dyn Error>` rather than only implement [`Display`], so their chain of
+/// sources can be printed. Without specialization, an impl for all `E: Display` could not tell errors with
+/// sources apart. Types that are only `Display` can implement [`Error`] without any methods.
+impl<T: Display, E: Into<Box<dyn Error>>> SolutionOutput for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, SolutionError> {
+        self.map_err(|e| SolutionError::Failed(e.into()))
+    }
+}
+
+/// Reason a solution part did not produce an answer.
+#[derive(Debug)]
+pub enum SolutionError {
+    /// The solution returned `None`.
+    NoAnswer,
+    /// The solution returned an error.
+    Failed(Box<dyn Error>),
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolutionError::NoAnswer => write!(f, "no answer"),
+            SolutionError::Failed(e) => {
+                write!(f, "{e}")?;
+                let mut source = e.source();
+                while let Some(e) = source {
+                    write!(f, "\n  caused by: {e}")?;
+                    source = e.source();
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Applies benchmark isolation if the runner was invoked with `--time --isolate`.
+/// Prints the applied settings on a line of their own, so they can be stored with the timings.
+pub fn isolate() {
+    let args: Vec<String> = env::args().collect();
+    if !args.contains(&"--time".into()) || !args.contains(&"--isolate".into()) {
+        return;
+    }
+
+    let isolation = isolate::apply();
+    if isolation.cpu.is_none() {
+        eprintln!("Warning: could not pin the benchmark to a single core.");
+    }
+    if !isolation.is_governor_ok() {
+        eprintln!(
+            "Warning: the CPU frequency governor is `{}`, not `{PERFORMANCE_GOVERNOR}`. Timings may fluctuate with the CPU frequency.",
+            isolation.governor.as_deref().unwrap_or_default()
+        );
+    }
+    println!("Isolation: {isolation}");
+}
+
+pub fn run_part<I: Clone, O: SolutionOutput>(func: impl Fn(I) -> O, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, cold_time, bench, counters) =
+        run_timed(func, input, SolutionOutput::into_answer, |result| {
+            print_result(result, &part_str, "")
+        });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&cold_time, bench, counters),
+    );
 
-    if let Some(result) = result {
+    if let Ok(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Times the parser passed to `solution!` as `parse = <fn>` like a part, and prints it as a `Parse:` line.
+/// The parsed value is only used for timing, the parts still parse the input themselves.
+pub fn run_parse<O>(func: impl Fn(&str) -> O, input: &str) {
+    let (_, cold_time, bench, counters) =
+        run_timed(func, input, Ok::<O, Infallible>, |_| print!("Parse:"));
+
+    println!("\rParse:{}", format_duration(&cold_time, bench, counters));
+}
+
+/// Time spent measuring a single input variant when scaling.
+const SCALE_BUDGET: Duration = Duration::from_millis(200);
+
+/// Larger input variants are skipped once a single run takes longer than this.
+const SCALE_LIMIT: Duration = Duration::from_secs(2);
+
+/// Times a solution part on inputs of growing size derived from `input`, and prints the complexity model that fits best.
+/// Variants the part fails or panics on are skipped. If too few prefixes of a line-oriented input succeed,
+/// subsets of its lines over growing sets of words are tried instead.
+pub fn scale_part<O: SolutionOutput>(func: impl Fn(&str) -> O, input: &str, part: u8) {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    // failing on partial input is expected, silence the panic messages.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let (unit, variants) = scaling::variants(input);
+    let mut samples = time_variants(&func, unit, &variants);
+
+    if samples.len() < 3 && unit == Unit::Lines {
+        println!("{ANSI_ITALIC}Too few prefixes succeeded, retrying with subsets of lines over growing sets of words.{ANSI_RESET}");
+        samples = time_variants(&func, unit, &scaling::word_subsets(input));
+    }
+
+    panic::set_hook(default_hook);
+
+    let fits = scaling::fit(&samples);
+    let Some((best, others)) = fits.split_first() else {
+        println!("Not enough successful runs to fit a model.");
+        return;
+    };
+
+    let others = others
+        .iter()
+        .map(|fit| format!("{} {:.2}", fit.model, fit.error))
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!(
+        "Best fit: {ANSI_BOLD}{}{ANSI_RESET} (error {:.2}; {others})",
+        best.model, best.error
+    );
+}
+
+/// Times `func` on each variant and prints a table of the timings.
+/// Returns the `(size, nanos)` samples of the variants the part succeeded on.
+fn time_variants<O: SolutionOutput>(
+    func: &impl Fn(&str) -> O,
+    unit: Unit,
+    variants: &[Variant],
+) -> Vec<(f64, f64)> {
+    println!("{:>10} {:>12}", unit.to_string(), "time");
+
+    let mut samples = vec![];
+    for variant in variants {
+        let Some(nanos) = time_variant(func, &variant.input) else {
+            println!("{:>10} {:>12}", variant.size, "✖");
+            continue;
+        };
+
+        println!("{:>10} {:>12}", variant.size, format_nanos(nanos));
+        #[allow(clippy::cast_precision_loss)]
+        samples.push((variant.size as f64, nanos));
+
+        if nanos > SCALE_LIMIT.as_nanos() as f64 {
+            println!("{ANSI_ITALIC}Skipping larger inputs, a single run took longer than {SCALE_LIMIT:?}.{ANSI_RESET}");
+            break;
+        }
+    }
+    samples
+}
+
+/// Median run time of `func` on `input` in nanoseconds, or `None` if the part fails or panics on it.
+fn time_variant<O: SolutionOutput>(func: &impl Fn(&str) -> O, input: &str) -> Option<f64> {
+    let mut timings = vec![];
+    let start = Instant::now();
+
+    while timings.len() < 3 || (start.elapsed() < SCALE_BUDGET && timings.len() < 1000) {
+        let timer = Instant::now();
+        let output = panic::catch_unwind(AssertUnwindSafe(|| func(black_box(input)))).ok()?;
+        timings.push(timer.elapsed());
+        output.into_answer().ok()?;
+
+        if timings[0] > SCALE_LIMIT {
+            break;
+        }
+    }
+
+    timings.sort_unstable();
+    Some(timings[timings.len() / 2].as_nanos() as f64)
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///
+/// Parts that fail to produce an answer are not benched.
+/// The first execution is cold and reported separately from the benched, warm ones.
+/// With the `count-allocs` feature, heap usage is measured during the first execution.
+/// Nodes expanded and queued by [`search`](crate::search) functions are counted during the first execution.
+fn run_timed<I: Clone, O, T, E>(
+    func: impl Fn(I) -> O,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    convert: impl Fn(O) -> Result<T, E>,
+    hook: impl Fn(&Result<T, E>),
+) -> (Result<T, E>, Duration, Option<Bench>, Counters) {
     let timer = Instant::now();
-    let result = {
+    let (result, counters) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        let snapshot = crate::template::alloc::Snapshot::start();
+
+        let search_snapshot = search::Snapshot::start();
+        let result = func(input);
+        let searched = search_snapshot.finish();
+
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        let allocs = Some(snapshot.finish());
+        #[cfg(not(all(feature = "count-allocs", not(feature = "dhat-heap"))))]
+        let allocs = None;
+
+        (result, Counters { allocs, searched })
     };
-    let base_time = timer.elapsed();
+    let cold_time = timer.elapsed();
+    let result = convert(result);
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    let bench = (result.is_ok() && std::env::args().any(|x| x == "--time"))
+        .then(|| bench(func, input, &cold_time));
+
+    (result, cold_time, bench, counters)
+}
+
+/// What was counted during the first execution of a part.
+#[derive(Clone, Copy, Debug, Default)]
+struct Counters {
+    allocs: Option<AllocStats>,
+    searched: Option<search::Stats>,
+}
 
-    (result, run.0, run.1)
+/// Cost and granularity of reading the clock.
+#[derive(Clone, Copy, Debug)]
+struct TimerCalibration {
+    /// Median time between two consecutive calls of [`Instant::now`], in nanoseconds.
+    overhead: f64,
+    /// Smallest non-zero time between two consecutive calls of [`Instant::now`], in nanoseconds.
+    resolution: f64,
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Measures the clock once per process.
+fn calibration() -> TimerCalibration {
+    static CALIBRATION: OnceLock<TimerCalibration> = OnceLock::new();
+
+    *CALIBRATION.get_or_init(|| {
+        let mut deltas: Vec<u128> = (0..1000)
+            .map(|_| {
+                let start = Instant::now();
+                (Instant::now() - start).as_nanos()
+            })
+            .collect();
+        deltas.sort_unstable();
+
+        #[allow(clippy::cast_precision_loss)]
+        TimerCalibration {
+            overhead: deltas[deltas.len() / 2] as f64,
+            resolution: deltas.iter().copied().find(|d| *d > 0).unwrap_or(1) as f64,
+        }
+    })
+}
+
+/// A timestamp should span at least this many multiples of the timer overhead to keep its share negligible.
+const OVERHEAD_FACTOR: f64 = 100.0;
+
+/// Calls of fast parts are batched until a timestamp spans at least this many nanoseconds.
+const MIN_BATCH_NANOS: f64 = 1000.0;
+
+/// Outcome of benching a part.
+#[derive(Clone, Copy, Debug)]
+struct Bench {
+    /// Average warm run time of a single call, in nanoseconds.
+    nanos: f64,
+    /// Number of calls the average was taken over.
+    samples: u128,
+    /// Smallest difference in run time per call that the measurement can resolve, in nanoseconds.
+    resolution: f64,
+}
+
+/// Number of calls to measure per timestamp, so that the timer overhead is negligible.
+fn batch_size(call_nanos: f64, calibration: TimerCalibration) -> u128 {
+    let target = (calibration.overhead * OVERHEAD_FACTOR).max(MIN_BATCH_NANOS);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let size = (target / call_nanos.max(1.0)).ceil() as u128;
+    size.max(1)
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, cold_time: &Duration) -> Bench {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let calibration = calibration();
+
+    // the cold run overestimates fast parts, estimate the warm run time with a few calls instead.
+    #[allow(clippy::cast_precision_loss)]
+    let warm_nanos = if cold_time.as_nanos() < 1_000_000 {
+        const WARMUP_CALLS: u32 = 10;
+        let timer = Instant::now();
+        for _ in 0..WARMUP_CALLS {
+            black_box(func(black_box(input.clone())));
+        }
+        timer.elapsed().as_nanos() as f64 / f64::from(WARMUP_CALLS)
+    } else {
+        cold_time.as_nanos() as f64
+    };
+
+    let batch = batch_size(warm_nanos, calibration);
 
-    let mut timers: Vec<Duration> = vec![];
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let bench_iterations = (Duration::from_secs(1).as_nanos()
+        / cmp::max((warm_nanos * batch as f64) as u128, 10))
+    .clamp(10, 10000);
+
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
 
     for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
+        // need clones here to make the borrow checker happy, they are created outside of the measurement.
+        let inputs: Vec<I> = (0..batch).map(|_| input.clone()).collect();
         let timer = Instant::now();
-        black_box(func(black_box(cloned)));
+        for cloned in inputs {
+            black_box(func(black_box(cloned)));
+        }
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    #[allow(clippy::cast_precision_loss)]
+    let batch_f64 = batch as f64;
+
+    Bench {
+        nanos: ((average_nanos(&timers) - calibration.overhead) / batch_f64).max(0.0),
+        samples: bench_iterations * batch,
+        resolution: calibration.resolution / batch_f64,
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
+#[allow(clippy::cast_precision_loss)]
+fn average_nanos(numbers: &[Duration]) -> f64 {
     numbers
         .iter()
         .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+        .sum::<u128>() as f64
+        / numbers.len() as f64
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Formats nanoseconds like [`Duration`]'s `Debug`, with as many decimals as `resolution` supports (at least 1, at most 3).
+fn format_precise(nanos: f64, resolution: f64) -> String {
+    let (unit, scale) = match nanos {
+        n if n >= 1e9 => ("s", 1e9),
+        n if n >= 1e6 => ("ms", 1e6),
+        n if n >= 1e3 => ("µs", 1e3),
+        _ => ("ns", 1.0),
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let decimals = (-(resolution / scale).log10().floor()).clamp(1.0, 3.0) as usize;
+
+    format!("{:.decimals$}{unit}", nanos / scale)
+}
+
+fn format_duration(cold_time: &Duration, bench: Option<Bench>, counters: Counters) -> String {
+    let allocs = counters
+        .allocs
+        .map(|a| format!(", {a}"))
+        .unwrap_or_default();
+    let searched = counters
+        .searched
+        .map(|s| format!(", {s}"))
+        .unwrap_or_default();
+    match bench {
+        None => format!(" ({cold_time:.1?}{allocs}{searched})"),
+        Some(bench) => format!(
+            " ({} @ {} samples, cold {cold_time:.1?}{allocs}{searched})",
+            format_precise(bench.nanos, bench.resolution),
+            bench.samples
+        ),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Result<T, SolutionError>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +439,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Err(SolutionError::NoAnswer) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,6 +447,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(error) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {ANSI_BOLD}error:{ANSI_RESET} {error}");
+            }
+        }
     }
 }
 
@@ -165,5 +494,113 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let output = aoc_cli::submit(day, part, &answer);
+
+    if output.as_ref().is_ok_and(aoc_cli::is_accepted) {
+        let mut answers = Answers::read_from_file();
+        answers.record(day, part, &answer);
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to record the accepted answer: {e}");
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{batch_size, format_precise, SolutionError, SolutionOutput, TimerCalibration};
+    use std::{error::Error, fmt::Display};
+
+    #[derive(Debug)]
+    struct Outer(std::num::ParseIntError);
+
+    impl Display for Outer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "could not parse box dimensions")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(1).into_answer().unwrap(), 1);
+        assert!(matches!(
+            None::<u32>.into_answer(),
+            Err(SolutionError::NoAnswer)
+        ));
+    }
+
+    #[test]
+    fn converts_string_errors() {
+        let result: Result<u32, String> = Err("bad input".into());
+        assert_eq!(result.into_answer().unwrap_err().to_string(), "bad input");
+    }
+
+    #[test]
+    fn converts_display_errors_with_an_empty_error_impl() {
+        #[derive(Debug)]
+        enum Malformed {
+            Line(usize),
+        }
+
+        impl Display for Malformed {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Malformed::Line(line) => write!(f, "malformed line {line}"),
+                }
+            }
+        }
+
+        impl Error for Malformed {}
+
+        let result: Result<u32, Malformed> = Err(Malformed::Line(3));
+        assert_eq!(
+            result.into_answer().unwrap_err().to_string(),
+            "malformed line 3"
+        );
+    }
+
+    #[test]
+    fn renders_source_chain() {
+        let result: Result<u32, Outer> = Err(Outer("x".parse::<u32>().unwrap_err()));
+        assert_eq!(
+            result.into_answer().unwrap_err().to_string(),
+            "could not parse box dimensions\n  caused by: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn batches_fast_calls() {
+        let calibration = TimerCalibration {
+            overhead: 20.0,
+            resolution: 1.0,
+        };
+        // a timestamp should span at least 100 times the overhead.
+        assert_eq!(batch_size(5.0, calibration), 400);
+        assert_eq!(batch_size(1500.0, calibration), 2);
+        assert_eq!(batch_size(1e6, calibration), 1);
+        // timers with a small overhead still batch until a timestamp spans 1µs.
+        let calibration = TimerCalibration {
+            overhead: 1.0,
+            resolution: 1.0,
+        };
+        assert_eq!(batch_size(10.0, calibration), 100);
+    }
+
+    #[test]
+    fn formats_with_resolution() {
+        assert_eq!(format_precise(74.1349, 1.0), "74.1ns");
+        assert_eq!(format_precise(3.2749, 0.01), "3.27ns");
+        assert_eq!(format_precise(3.2749, 0.0001), "3.275ns");
+        assert_eq!(format_precise(1_234_567.0, 1.0), "1.235ms");
+        assert_eq!(format_precise(1_234_567.0, 1000.0), "1.235ms");
+        assert_eq!(format_precise(1_234_567.0, 100_000.0), "1.2ms");
+    }
 }
@@ -0,0 +1,293 @@
+/// Estimates how a solution scales by timing it on inputs of growing size and fitting complexity models.
+use std::fmt::Display;
+
+/// Inputs with at least this many lines are scaled by taking prefixes of their lines.
+const MIN_LINES: usize = 8;
+
+/// Number of evenly spaced sizes an input is cut into.
+const STEPS: usize = 8;
+
+/// Factors a single-line input is repeated by, in addition to its truncated prefixes.
+const REPETITIONS: [usize; 2] = [2, 4];
+
+/// What the size of a variant counts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Lines,
+    Bytes,
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unit::Lines => write!(f, "lines"),
+            Unit::Bytes => write!(f, "bytes"),
+        }
+    }
+}
+
+/// An input of a given size, derived from the real input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub size: usize,
+    pub input: String,
+}
+
+/// Derives inputs of growing size from `input`, ordered by size.
+/// Line-oriented inputs are cut into prefixes of their lines. Other inputs are truncated and repeated.
+pub fn variants(input: &str) -> (Unit, Vec<Variant>) {
+    let lines: Vec<&str> = input.lines().collect();
+
+    if lines.len() >= MIN_LINES {
+        let mut variants: Vec<Variant> = (1..=STEPS)
+            .map(|step| lines.len() * step / STEPS)
+            .map(|size| Variant {
+                size,
+                input: lines[..size].join("\n") + "\n",
+            })
+            .collect();
+        variants.dedup_by_key(|v| v.size);
+        return (Unit::Lines, variants);
+    }
+
+    let content = input.trim_end();
+    let newline = if content.len() < input.len() {
+        "\n"
+    } else {
+        ""
+    };
+
+    let mut variants: Vec<Variant> = (1..=STEPS)
+        .map(|step| content.len() * step / STEPS)
+        // truncate at character boundaries.
+        .map(|size| {
+            (0..=size)
+                .rev()
+                .find(|i| content.is_char_boundary(*i))
+                .unwrap_or(0)
+        })
+        .filter(|size| *size > 0)
+        .map(|size| Variant {
+            size,
+            input: content[..size].to_string() + newline,
+        })
+        .chain(REPETITIONS.into_iter().map(|times| Variant {
+            size: content.len() * times,
+            input: content.repeat(times) + newline,
+        }))
+        .collect();
+    variants.dedup_by_key(|v| v.size);
+    (Unit::Bytes, variants)
+}
+
+fn words(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.contains(char::is_alphabetic))
+}
+
+/// Derives inputs of growing size that only contain lines whose words are among the first `k` distinct words of `input`.
+/// Unlike prefixes, these keep inputs that describe relations complete, e.g. the distances between all pairs
+/// of the first `k` cities. Plain numbers are not words, so they do not limit which lines are kept.
+pub fn word_subsets(input: &str) -> Vec<Variant> {
+    let lines: Vec<(&str, Vec<&str>)> = input
+        .lines()
+        .map(|line| (line, words(line).collect()))
+        .collect();
+
+    let mut vocabulary: Vec<&str> = vec![];
+    for (_, words) in &lines {
+        for word in words {
+            if !vocabulary.contains(word) {
+                vocabulary.push(word);
+            }
+        }
+    }
+
+    let mut variants: Vec<Variant> = (1..=vocabulary.len())
+        .map(|k| {
+            let known = &vocabulary[..k];
+            let kept: Vec<&str> = lines
+                .iter()
+                .filter(|(_, words)| words.iter().all(|w| known.contains(w)))
+                .map(|(line, _)| *line)
+                .collect();
+            Variant {
+                size: kept.len(),
+                input: kept.join("\n") + "\n",
+            }
+        })
+        .filter(|v| v.size > 0)
+        .collect();
+    variants.dedup_by_key(|v| v.size);
+
+    // keep at most `STEPS` evenly spaced variants, always including the full input.
+    if variants.len() > STEPS {
+        let len = variants.len();
+        let picked: Vec<usize> = (1..=STEPS).map(|step| len * step / STEPS - 1).collect();
+        variants = picked.into_iter().map(|i| variants[i].clone()).collect();
+    }
+
+    variants
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Complexity model that timings are fitted against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Model {
+    Linear,
+    Linearithmic,
+    Quadratic,
+    /// `t = c * base^n`, with a fitted `base`.
+    Exponential {
+        base: f64,
+    },
+}
+
+impl Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Model::Linear => write!(f, "O(n)"),
+            Model::Linearithmic => write!(f, "O(n log n)"),
+            Model::Quadratic => write!(f, "O(n²)"),
+            Model::Exponential { base } => write!(f, "O({base:.2}ⁿ)"),
+        }
+    }
+}
+
+/// Result of fitting a model to timings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub model: Model,
+    /// Root mean square of the residuals in log space. Lower is better, `0.1` is roughly 10% off.
+    pub error: f64,
+}
+
+/// Fits `t = c * f(n)` for a fixed growth function `f`, in log space.
+fn fit_scaled(model: Model, samples: &[(f64, f64)], f: impl Fn(f64) -> f64) -> Fit {
+    let residuals: Vec<f64> = samples.iter().map(|(n, t)| t.ln() - f(*n).ln()).collect();
+    #[allow(clippy::cast_precision_loss)]
+    let log_c = residuals.iter().sum::<f64>() / residuals.len() as f64;
+    Fit {
+        model,
+        error: rms(residuals.iter().map(|r| r - log_c)),
+    }
+}
+
+/// Fits `t = c * base^n` by a linear regression of `ln t` on `n`.
+fn fit_exponential(samples: &[(f64, f64)]) -> Fit {
+    #[allow(clippy::cast_precision_loss)]
+    let len = samples.len() as f64;
+    let mean_n = samples.iter().map(|(n, _)| n).sum::<f64>() / len;
+    let mean_log_t = samples.iter().map(|(_, t)| t.ln()).sum::<f64>() / len;
+
+    let covariance: f64 = samples
+        .iter()
+        .map(|(n, t)| (n - mean_n) * (t.ln() - mean_log_t))
+        .sum();
+    let variance: f64 = samples.iter().map(|(n, _)| (n - mean_n).powi(2)).sum();
+    let slope = if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    };
+
+    Fit {
+        model: Model::Exponential { base: slope.exp() },
+        error: rms(samples
+            .iter()
+            .map(|(n, t)| t.ln() - (mean_log_t + slope * (n - mean_n)))),
+    }
+}
+
+fn rms(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0.0), |(sum, count), v| (sum + v * v, count + 1.0));
+    (sum / count).sqrt()
+}
+
+/// Fits all models to `(size, nanos)` samples, ordered from best to worst fit.
+/// Returns no fits for fewer than three samples, as any model fits two points.
+pub fn fit(samples: &[(f64, f64)]) -> Vec<Fit> {
+    if samples.len() < 3 {
+        return vec![];
+    }
+
+    // trivial parts can take 0ns on coarse clocks, which has no logarithm.
+    let samples: Vec<(f64, f64)> = samples.iter().map(|(n, t)| (*n, t.max(1.0))).collect();
+
+    let mut fits = vec![
+        fit_scaled(Model::Linear, &samples, |n| n),
+        fit_scaled(Model::Linearithmic, &samples, |n| n * n.log2().max(1.0)),
+        fit_scaled(Model::Quadratic, &samples, |n| n * n),
+        fit_exponential(&samples),
+    ];
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, variants, word_subsets, Model, Unit};
+
+    #[test]
+    fn cuts_lines_into_prefixes() {
+        let input = (1..=16).map(|i| format!("{i}\n")).collect::<String>();
+        let (unit, variants) = variants(&input);
+        assert_eq!(unit, Unit::Lines);
+        assert_eq!(variants.len(), 8);
+        assert_eq!(variants[0].size, 2);
+        assert_eq!(variants[0].input, "1\n2\n");
+        assert_eq!(variants[7].input, input);
+    }
+
+    #[test]
+    fn truncates_and_repeats_single_lines() {
+        let (unit, variants) = variants("abcdefgh\n");
+        assert_eq!(unit, Unit::Bytes);
+        let sizes: Vec<usize> = variants.iter().map(|v| v.size).collect();
+        assert_eq!(sizes, vec![1, 2, 3, 4, 5, 6, 7, 8, 16, 32]);
+        assert_eq!(variants[2].input, "abc\n");
+        assert_eq!(variants[8].input, "abcdefghabcdefgh\n");
+    }
+
+    #[test]
+    fn keeps_relations_complete() {
+        let input = "A to B = 1\nA to C2 = 2\nB to C2 = 3\nA to D = 4\nB to D = 5\nC2 to D = 6\n";
+        let variants = word_subsets(input);
+        let sizes: Vec<usize> = variants.iter().map(|v| v.size).collect();
+        assert_eq!(sizes, vec![1, 3, 6]);
+        assert_eq!(variants[1].input, "A to B = 1\nA to C2 = 2\nB to C2 = 3\n");
+    }
+
+    #[test]
+    fn fits_models() {
+        let samples = |f: fn(f64) -> f64| -> Vec<(f64, f64)> {
+            (1..=8)
+                .map(|i| f64::from(i) * 10.0)
+                .map(|n| (n, f(n)))
+                .collect()
+        };
+
+        assert_eq!(fit(&samples(|n| 3.0 * n))[0].model, Model::Linear);
+        assert_eq!(fit(&samples(|n| n * n + 1.0))[0].model, Model::Quadratic);
+
+        let exponential = fit(&samples(|n| 1.1_f64.powf(n)))[0];
+        assert!(
+            matches!(exponential.model, Model::Exponential { base } if (base - 1.1).abs() < 1e-6)
+        );
+        assert_eq!(exponential.model.to_string(), "O(1.10ⁿ)");
+
+        assert!(fit(&samples(|n| n)[..2]).is_empty());
+    }
+
+    #[test]
+    fn fits_zero_timings() {
+        let samples = [(1.0, 0.0), (2.0, 0.0), (4.0, 2.0), (8.0, 4.0)];
+        let fits = fit(&samples);
+        assert_eq!(fits.len(), 4);
+        assert!(fits.iter().all(|f| f.error.is_finite()));
+        assert!(fits
+            .iter()
+            .all(|f| !matches!(f.model, Model::Exponential { base } if !base.is_finite())));
+    }
+}
@@ -1,91 +1,380 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
-use tinyjson::JsonValue;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::template::{alloc::AllocStats, fingerprint::Fingerprint, isolate::Isolation, Day};
+
+pub(crate) static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+
+/// Version of the `timings.json` schema written by this crate.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+}
+
+/// Represents the benchmark of a single part.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PartTiming {
+    pub status: PartStatus,
+    /// Average run time in nanoseconds. Only present for solved parts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nanos: Option<f64>,
+    /// Number of runs the average was taken over. Not known for migrated timings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub samples: Option<u64>,
+    /// Run time of the first, cold call in nanoseconds, which is not part of the average.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cold_nanos: Option<f64>,
+    /// Unix timestamp (in seconds) of when the part was run. Not known for migrated timings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<u64>,
+    /// Id of the [`Fingerprint`] of the machine the part was run on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<String>,
+    /// Peak heap usage in bytes while the part ran. Only known if allocations were counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_bytes: Option<u64>,
+    /// Number of heap allocations while the part ran. Only known if allocations were counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<u64>,
+    /// Total bytes allocated while the part ran. Only known if allocations were counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc_bytes: Option<u64>,
+    /// Isolation settings the part was benched with. Only known if benched with `--isolate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isolation: Option<Isolation>,
+    /// Hash of the day's module when the part was run, see [`source_hash`](crate::template::run_multi::source_hash).
+    /// Not known for timings recorded before hashes were stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_hash: Option<String>,
+}
+
+impl PartTiming {
+    pub fn solved(nanos: f64, samples: Option<u64>) -> Self {
+        Self {
+            status: PartStatus::Solved,
+            nanos: Some(nanos),
+            samples,
+            cold_nanos: None,
+            recorded_at: None,
+            machine: None,
+            peak_bytes: None,
+            allocations: None,
+            alloc_bytes: None,
+            isolation: None,
+            source_hash: None,
+        }
+    }
+
+    pub fn unsolved() -> Self {
+        Self {
+            status: PartStatus::Unsolved,
+            nanos: None,
+            samples: None,
+            cold_nanos: None,
+            recorded_at: None,
+            machine: None,
+            peak_bytes: None,
+            allocations: None,
+            alloc_bytes: None,
+            isolation: None,
+            source_hash: None,
+        }
+    }
+
+    pub fn failed() -> Self {
+        Self {
+            status: PartStatus::Failed,
+            nanos: None,
+            samples: None,
+            cold_nanos: None,
+            recorded_at: None,
+            machine: None,
+            peak_bytes: None,
+            allocations: None,
+            alloc_bytes: None,
+            isolation: None,
+            source_hash: None,
+        }
+    }
+
+    /// Sets the time the part was run to now.
+    #[must_use]
+    pub fn recorded_now(mut self) -> Self {
+        self.recorded_at = Some(unix_now());
+        self
+    }
+
+    /// Sets the heap usage of the part.
+    #[must_use]
+    pub fn with_allocs(mut self, stats: AllocStats) -> Self {
+        self.allocations = Some(stats.allocations);
+        self.alloc_bytes = Some(stats.bytes);
+        self.peak_bytes = Some(stats.peak);
+        self
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.status == PartStatus::Solved
+    }
+}
+
+/// How `Timings::merge` decides between a stored and a new timing of the same part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep whichever solved timing is faster.
+    Best,
+    /// Always keep the new timing, even if the part failed.
+    Latest,
+    /// Keep the new timing only if the part was solved.
+    #[default]
+    Successful,
+}
+
+impl MergePolicy {
+    fn pick(self, stored: Option<&PartTiming>, new: Option<&PartTiming>) -> Option<PartTiming> {
+        let picked = match (stored, new) {
+            (stored, None) => stored,
+            (None, new) => new,
+            (Some(stored), Some(new)) => match self {
+                MergePolicy::Latest => Some(new),
+                MergePolicy::Successful if new.is_solved() || !stored.is_solved() => Some(new),
+                MergePolicy::Successful => Some(stored),
+                MergePolicy::Best => match (stored.nanos, new.nanos) {
+                    _ if !new.is_solved() && stored.is_solved() => Some(stored),
+                    _ if !stored.is_solved() => Some(new),
+                    (Some(a), Some(b)) if a <= b => Some(stored),
+                    _ => Some(new),
+                },
+            },
+        };
+        picked.cloned()
+    }
+}
 
-use crate::template::Day;
+impl FromStr for MergePolicy {
+    type Err = String;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "best" => Ok(MergePolicy::Best),
+            "latest" => Ok(MergePolicy::Latest),
+            "successful" => Ok(MergePolicy::Successful),
+            _ => Err(format!(
+                "unknown merge policy `{s}`, expected `best`, `latest` or `successful`."
+            )),
+        }
+    }
+}
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// Average run time of parsing the input in nanoseconds, for solutions that report it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_nanos: Option<f64>,
+}
+
+impl Timing {
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            part_1: None,
+            part_2: None,
+            parse_nanos: None,
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn part_mut(&mut self, part: u8) -> Option<&mut Option<PartTiming>> {
+        match part {
+            1 => Some(&mut self.part_1),
+            2 => Some(&mut self.part_2),
+            _ => None,
+        }
+    }
+
+    fn machine_ids(&self) -> Vec<&String> {
+        [&self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.machine.as_ref())
+            .collect()
+    }
+
+    /// Sum of the run times of all solved parts.
+    pub fn total_nanos(&self) -> f64 {
+        [&self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.nanos)
+            .sum()
+    }
 }
 
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// Fingerprints of the machines the parts were run on, by id.
+    pub machines: BTreeMap<String, Fingerprint>,
+}
+
+/// On-disk representation of [`Timings`].
+#[derive(Serialize, Deserialize)]
+struct TimingsFile {
+    version: u64,
+    data: Vec<Timing>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    machines: BTreeMap<String, Fingerprint>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
-        let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        fs::write(TIMINGS_FILE_PATH, self.to_json())
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
+    /// Rehydrate timings from a JSON file. If not present or unreadable, returns empty timings.
+    /// Files in an older schema are migrated in memory and upgraded on the next store.
+    ///
+    /// Only for reading, as storing the result would replace an unreadable file. Use [`Timings::try_read_from_file`]
+    /// before storing.
     pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
+        Self::try_read_from_file().unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Timings::default()
+        })
+    }
 
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
-            }
+    /// Like [`Timings::read_from_file`], but fails if the file exists and can't be read, e.g. because it has an
+    /// unsupported version or is malformed.
+    pub fn try_read_from_file() -> Result<Self, String> {
+        Self::from_file_content(fs::read_to_string(TIMINGS_FILE_PATH))
+    }
+
+    fn from_file_content(content: io::Result<String>) -> Result<Self, String> {
+        match content {
+            Ok(content) => Timings::try_from(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(format!("could not read \"{TIMINGS_FILE_PATH}\": {e}")),
         }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
+    pub fn to_json(&self) -> String {
+        let file = TimingsFile {
+            version: SCHEMA_VERSION,
+            data: self.data.clone(),
+            machines: self.machines.clone(),
+        };
+        // serializing plain structs to a string does not fail.
+        serde_json::to_string_pretty(&file).unwrap() + "\n"
+    }
 
-        for timing in &new.data {
-            data.push(timing.clone());
-        }
+    /// Merge two sets of timings part by part, deciding between stored and new parts with `policy`.
+    /// Parts that were not run keep their stored timing.
+    pub fn merge(&self, new: &Self, policy: MergePolicy) -> Self {
+        let mut data: Vec<Timing> = self.data.clone();
 
-        for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
-                data.push(timing.clone());
+        for timing in &new.data {
+            match data.iter_mut().find(|t| t.day == timing.day) {
+                Some(stored) => {
+                    for part in [1, 2] {
+                        let picked = policy.pick(stored.part(part), timing.part(part));
+                        if let Some(slot) = stored.part_mut(part) {
+                            *slot = picked;
+                        }
+                    }
+                    stored.parse_nanos = timing.parse_nanos.or(stored.parse_nanos);
+                }
+                None => data.push(timing.clone()),
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+
+        // only keep fingerprints of machines that parts were run on.
+        let machines = self
+            .machines
+            .iter()
+            .chain(&new.machines)
+            .filter(|(id, _)| data.iter().any(|t| t.machine_ids().contains(id)))
+            .map(|(id, fingerprint)| (id.clone(), fingerprint.clone()))
+            .collect();
+
+        Timings { data, machines }
+    }
+
+    /// Tags all parts with the fingerprint of the machine they were run on.
+    pub fn tag_machine(&mut self, fingerprint: &Fingerprint) {
+        let id = fingerprint.id();
+        self.data
+            .iter_mut()
+            .flat_map(|t| [&mut t.part_1, &mut t.part_2])
+            .flatten()
+            .for_each(|part| part.machine = Some(id.clone()));
+        self.machines.insert(id, fingerprint.clone());
+    }
+
+    /// Fingerprints of stored parts that were run on a different machine than `fingerprint`.
+    pub fn other_machines(&self, fingerprint: &Fingerprint) -> Vec<&Fingerprint> {
+        let id = fingerprint.id();
+        self.machines
+            .iter()
+            .filter(|(other_id, _)| **other_id != id)
+            .filter(|(other_id, _)| self.data.iter().any(|t| t.machine_ids().contains(other_id)))
+            .map(|(_, fingerprint)| fingerprint)
+            .collect()
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            t.day == day
+                && t.part_1.as_ref().is_some_and(PartTiming::is_solved)
+                && t.part_2.as_ref().is_some_and(PartTiming::is_solved)
+        })
     }
 }
 
-/* -------------------------------------------------------------------------- */
-
-impl From<Timings> for JsonValue {
-    fn from(value: Timings) -> Self {
-        let mut map: HashMap<String, JsonValue> = HashMap::new();
-
-        map.insert(
-            "data".into(),
-            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
-        );
-
-        JsonValue::Object(map)
+/// Returns the schema version of a `timings.json` document. Documents without a version are version 1.
+pub fn schema_version(value: &str) -> Result<u64, String> {
+    let json: Value = serde_json::from_str(value).or(Err("not valid JSON file."))?;
+    match json.get("version") {
+        None => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| "expected `json.version` to be a number.".to_string()),
     }
 }
 
@@ -93,91 +382,115 @@ impl TryFrom<String> for Timings {
     type Error = String;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
-
-        let json_data = json
-            .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
-            .get("data")
-            .ok_or("expected JSON document to have key `data`.")?
-            .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
-
-        Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+        match schema_version(&value)? {
+            1 => v1::migrate(&value),
+            SCHEMA_VERSION => serde_json::from_str::<TimingsFile>(&value)
+                .map(|file| Timings {
+                    data: file.data,
+                    machines: file.machines,
+                })
+                .map_err(|e| format!("malformed timings: {e}")),
+            v => Err(format!(
+                "unsupported timings version {v}, expected at most {SCHEMA_VERSION}."
+            )),
+        }
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-impl From<&Timing> for JsonValue {
-    fn from(value: &Timing) -> Self {
-        let mut map: HashMap<String, JsonValue> = HashMap::new();
+/// Current time as a unix timestamp in seconds.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+/// Formats nanoseconds like the runner prints durations, e.g. `74.1ns` or `1.5ms`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+/// Parses a duration printed with `Debug`, e.g. `74.13ns`, to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+/// The original schema, which stored per-part timings as formatted strings.
+mod v1 {
+    use serde::Deserialize;
+
+    use super::{parse_duration, PartTiming, Timing, Timings};
+    use crate::template::Day;
 
-        JsonValue::Object(map)
+    #[derive(Deserialize)]
+    struct TimingsFile {
+        data: Vec<TimingV1>,
     }
-}
 
-impl TryFrom<&JsonValue> for Timing {
-    type Error = String;
+    #[derive(Deserialize)]
+    struct TimingV1 {
+        day: Day,
+        part_1: Option<String>,
+        part_2: Option<String>,
+        #[allow(dead_code)]
+        total_nanos: f64,
+        #[serde(default)]
+        failed_parts: Vec<u8>,
+    }
 
-    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
-        let json = value
-            .get::<HashMap<String, JsonValue>>()
-            .ok_or("Expected timing to be a JSON object.")?;
-
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
-
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
-
-        Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+    fn migrate_part(
+        formatted: Option<&str>,
+        failed: bool,
+        part: &str,
+    ) -> Result<Option<PartTiming>, String> {
+        match formatted {
+            _ if failed => Ok(Some(PartTiming::failed())),
+            None => Ok(None),
+            Some(s) => parse_duration(s)
+                .map(|nanos| Some(PartTiming::solved(nanos, None)))
+                .ok_or_else(|| format!("could not parse timing.{part} `{s}`.")),
+        }
+    }
+
+    pub fn migrate(value: &str) -> Result<Timings, String> {
+        let file: TimingsFile =
+            serde_json::from_str(value).map_err(|e| format!("malformed timings: {e}"))?;
+
+        let data = file
+            .data
+            .into_iter()
+            .map(|t| {
+                Ok(Timing {
+                    day: t.day,
+                    part_1: migrate_part(
+                        t.part_1.as_deref(),
+                        t.failed_parts.contains(&1),
+                        "part_1",
+                    )?,
+                    part_2: migrate_part(
+                        t.part_2.as_deref(),
+                        t.failed_parts.contains(&2),
+                        "part_2",
+                    )?,
+                    parse_nanos: None,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Timings {
+            data,
+            ..Timings::default()
         })
     }
 }
@@ -188,51 +501,60 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(PartTiming::solved(1e+10, Some(10))),
+                    part_2: Some(PartTiming::solved(2e+10, Some(10))),
+                    parse_nanos: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(PartTiming::solved(3e+10, Some(10))),
+                    part_2: Some(PartTiming::solved(4e+10, Some(10))),
+                    parse_nanos: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(PartTiming::solved(4e+10, Some(10))),
                     part_2: None,
-                    total_nanos: 4e+10,
+                    parse_nanos: None,
                 },
             ],
+            ..Default::default()
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::io;
+
+        use crate::{
+            day,
+            template::timings::{PartStatus, PartTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "status": "solved", "nanos": 1000000, "samples": 100 }, "part_2": { "status": "failed" } }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming::solved(1_000_000_f64, Some(100)))
+            );
+            assert_eq!(timing.part_2.as_ref().unwrap().status, PartStatus::Failed);
+            assert_eq!(timing.total_nanos(), 1_000_000_f64);
         }
 
         #[test]
         fn handles_empty_timings() {
-            let json = r#"{ "data": [] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
         }
@@ -247,38 +569,101 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_future_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        fn treats_missing_files_as_empty() {
+            let missing = Err(io::Error::from(io::ErrorKind::NotFound));
+            assert_eq!(Timings::from_file_content(missing).unwrap().data.len(), 0);
+        }
+
+        #[test]
+        fn fails_for_unreadable_files() {
+            let future = Ok(r#"{ "version": 3, "data": [] }"#.to_string());
+            assert!(Timings::from_file_content(future).is_err());
+            assert!(Timings::from_file_content(Ok("{".to_string())).is_err());
+            let denied = Err(io::Error::from(io::ErrorKind::PermissionDenied));
+            assert!(Timings::from_file_content(denied).is_err());
+        }
+    }
+
+    mod migration {
+        use crate::{
+            day,
+            template::timings::{PartStatus, PartTiming, Timings},
+        };
+
+        #[test]
+        fn migrates_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": null, "total_nanos": 1500000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.part_1, Some(PartTiming::solved(1_500_000_f64, None)));
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn migrates_v1_failed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "failed_parts": [2] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(
+                timings.data[0].part_2.as_ref().unwrap().status,
+                PartStatus::Failed
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
 
     mod serialization {
         use super::get_mock_timings;
-        use std::collections::HashMap;
-        use tinyjson::JsonValue;
+        use crate::template::timings::{schema_version, Timings, SCHEMA_VERSION};
 
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
-            assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
-                    .get("data")
-                    .unwrap()
-                    .get::<Vec<JsonValue>>()
-                    .unwrap()
-                    .len(),
-                3
-            );
+            let json = timings.to_json();
+            assert_eq!(schema_version(&json).unwrap(), SCHEMA_VERSION);
+            assert_eq!(Timings::try_from(json).unwrap(), timings);
+        }
+    }
+
+    mod durations {
+        use crate::template::timings::{format_nanos, parse_duration};
+
+        #[test]
+        fn formats_nanos() {
+            assert_eq!(format_nanos(74.0), "74.0ns");
+            assert_eq!(format_nanos(1_500_000.0), "1.5ms");
+        }
+
+        #[test]
+        fn parses_durations() {
+            assert_eq!(parse_duration("74.13ns"), Some(74.13));
+            assert_eq!(parse_duration("2s"), Some(2e9));
+            assert_eq!(parse_duration("fast"), None);
         }
     }
 
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -286,13 +671,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    part_1: Some(PartTiming::solved(1e6, Some(10))),
+                    part_2: Some(PartTiming::solved(2e6, Some(10))),
+                    parse_nanos: None,
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,50 +686,72 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    part_1: Some(PartTiming::solved(1e6, Some(10))),
+                    part_2: Some(PartTiming::failed()),
+                    parse_nanos: None,
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                fingerprint::Fingerprint,
+                timings::{MergePolicy, PartStatus, PartTiming, Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
 
-        #[test]
-        fn handles_disjunct_timings() {
-            let timings = get_mock_timings();
+        fn merge_part(
+            stored: Option<PartTiming>,
+            new: Option<PartTiming>,
+            policy: MergePolicy,
+        ) -> Option<PartTiming> {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: stored,
+                    part_2: None,
+                    parse_nanos: None,
+                }],
+                ..Default::default()
+            };
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
+                    day: day!(1),
+                    part_1: new,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    parse_nanos: None,
                 }],
+                ..Default::default()
             };
-            let merged = timings.merge(&other);
+            timings.merge(&other, policy).data[0].part_1.clone()
+        }
+
+        #[test]
+        fn handles_disjunct_timings() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing::new(day!(3))],
+                ..Default::default()
+            };
+            let merged = timings.merge(&other, MergePolicy::default());
             assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
@@ -358,17 +766,20 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
+                    part_1: Some(PartTiming::solved(1.0, Some(10))),
                     part_2: None,
-                    total_nanos: 0_f64,
+                    parse_nanos: None,
                 }],
+                ..Default::default()
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::default());
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1.as_ref().unwrap().nanos, Some(1.0));
+            // parts that were not run keep their stored timing.
+            assert_eq!(merged.data[1].part_2.as_ref().unwrap().nanos, Some(4e+10));
             assert_eq!(merged.data[2].day, day!(4));
         }
 
@@ -376,7 +787,7 @@ mod tests {
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::default());
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -384,8 +795,90 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::default());
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn successful_keeps_stored_parts_over_failures() {
+            let stored = Some(PartTiming::solved(10.0, Some(10)));
+            let merged = merge_part(
+                stored.clone(),
+                Some(PartTiming::failed()),
+                MergePolicy::Successful,
+            );
+            assert_eq!(merged, stored);
+
+            let merged = merge_part(
+                stored,
+                Some(PartTiming::solved(20.0, Some(10))),
+                MergePolicy::Successful,
+            );
+            assert_eq!(merged.unwrap().nanos, Some(20.0));
+        }
+
+        #[test]
+        fn successful_replaces_unsolved_parts() {
+            let merged = merge_part(
+                Some(PartTiming::unsolved()),
+                Some(PartTiming::failed()),
+                MergePolicy::Successful,
+            );
+            assert_eq!(merged.unwrap().status, PartStatus::Failed);
+        }
+
+        #[test]
+        fn latest_keeps_new_parts() {
+            let merged = merge_part(
+                Some(PartTiming::solved(10.0, Some(10))),
+                Some(PartTiming::failed()),
+                MergePolicy::Latest,
+            );
+            assert_eq!(merged.unwrap().status, PartStatus::Failed);
+        }
+
+        #[test]
+        fn keeps_referenced_machines() {
+            let fingerprint = Fingerprint {
+                cpu_model: "AMD Ryzen 7 5800X".into(),
+                cores: 16,
+                rustc: "rustc 1.83.0".into(),
+                target: "x86_64-unknown-linux-gnu".into(),
+                profile: "release".into(),
+                features: vec![],
+            };
+            let other = Fingerprint {
+                cores: 8,
+                ..fingerprint.clone()
+            };
+
+            let mut timings = get_mock_timings();
+            timings.tag_machine(&other);
+            assert_eq!(timings.other_machines(&fingerprint), vec![&other]);
+
+            let mut new = get_mock_timings();
+            new.tag_machine(&fingerprint);
+            let merged = timings.merge(&new, MergePolicy::Latest);
+            assert_eq!(merged.machines.len(), 1);
+            assert!(merged.other_machines(&fingerprint).is_empty());
+        }
+
+        #[test]
+        fn best_keeps_fastest_parts() {
+            let fast = Some(PartTiming::solved(10.0, Some(10)));
+            let slow = Some(PartTiming::solved(20.0, Some(10)));
+            assert_eq!(
+                merge_part(fast.clone(), slow.clone(), MergePolicy::Best),
+                fast
+            );
+            assert_eq!(
+                merge_part(slow.clone(), fast.clone(), MergePolicy::Best),
+                fast
+            );
+            assert_eq!(
+                merge_part(slow.clone(), Some(PartTiming::failed()), MergePolicy::Best),
+                slow
+            );
+        }
     }
 }
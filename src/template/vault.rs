@@ -0,0 +1,245 @@
+/// Authenticated encryption of puzzle inputs and descriptions, so they can be committed to the repository.
+///
+/// Every plaintext file (e.g. `data/inputs/01.txt`) is stored next to it as a blob with a `.vault` suffix
+/// (e.g. `data/inputs/01.txt.vault`). Blobs are encrypted with ChaCha20-Poly1305 and bound to their file
+/// name, so they can't be swapped around without failing to decrypt.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::data_dir;
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+const MAGIC: &[u8] = b"AOCVAULT1";
+const NONCE_LEN: usize = 12;
+const KEY_ENV: &str = "AOC_VAULT_KEY";
+const KEYFILE_ENV: &str = "AOC_VAULT_KEYFILE";
+const DEFAULT_KEYFILE: &str = ".vault-key";
+pub const EXTENSION: &str = "vault";
+
+#[derive(Debug)]
+pub enum VaultError {
+    MissingKey,
+    InvalidKey(String),
+    Corrupted(PathBuf),
+    IO(io::Error),
+}
+
+impl Display for VaultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultError::MissingKey => write!(
+                f,
+                "no vault key found. Set `{KEY_ENV}`, point `{KEYFILE_ENV}` to a keyfile or create `data/{DEFAULT_KEYFILE}` with `cargo vault keygen`."
+            ),
+            VaultError::InvalidKey(reason) => write!(f, "invalid vault key: {reason}"),
+            VaultError::Corrupted(path) => write!(
+                f,
+                "\"{}\" could not be decrypted, it is corrupted or was encrypted with a different key.",
+                path.display()
+            ),
+            VaultError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for VaultError {}
+
+impl From<io::Error> for VaultError {
+    fn from(e: io::Error) -> Self {
+        VaultError::IO(e)
+    }
+}
+
+/// Returns the path of the encrypted blob for a plaintext file.
+pub fn blob_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+fn keyfile_path() -> PathBuf {
+    env::var_os(KEYFILE_ENV).map_or_else(|| data_dir().join(DEFAULT_KEYFILE), PathBuf::from)
+}
+
+/// Loads the key from `AOC_VAULT_KEY`, or from the keyfile at `AOC_VAULT_KEYFILE` (default: `data/.vault-key`).
+/// Keys are 32 bytes, encoded as 64 hex characters.
+pub fn load_key() -> Result<Key, VaultError> {
+    let encoded = match env::var(KEY_ENV) {
+        Ok(key) => key,
+        Err(_) => match fs::read_to_string(keyfile_path()) {
+            Ok(key) => key,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(VaultError::MissingKey),
+            Err(e) => return Err(e.into()),
+        },
+    };
+
+    decode_key(encoded.trim())
+}
+
+fn decode_key(encoded: &str) -> Result<Key, VaultError> {
+    if encoded.len() != 64 || !encoded.is_ascii() {
+        return Err(VaultError::InvalidKey(
+            "expected 64 hexadecimal characters.".into(),
+        ));
+    }
+
+    let bytes = (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&encoded[i..i + 2], 16))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| VaultError::InvalidKey(e.to_string()))?;
+
+    Ok(*Key::from_slice(&bytes))
+}
+
+fn encode_key(key: &Key) -> String {
+    key.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Generates a new key and writes it to the keyfile. Fails if the keyfile exists.
+/// On unix, the keyfile is only readable and writable by its owner.
+pub fn generate_keyfile() -> Result<PathBuf, VaultError> {
+    let path = keyfile_path();
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(&path)
+        .and_then(|mut file| io::Write::write_all(&mut file, encode_key(&key).as_bytes()))?;
+    Ok(path)
+}
+
+/// Binds a blob to the name of its plaintext file.
+fn associated_data(path: &Path) -> Vec<u8> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().as_bytes().to_vec())
+        .unwrap_or_default()
+}
+
+fn encrypt(key: &Key, path: &Path, plaintext: &[u8]) -> Vec<u8> {
+    let cipher = ChaCha20Poly1305::new(key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let aad = associated_data(path);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &aad,
+            },
+        )
+        .expect("encryption of an in-memory buffer does not fail");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+fn decrypt(key: &Key, path: &Path, blob: &[u8]) -> Option<Vec<u8>> {
+    let rest = blob.strip_prefix(MAGIC)?;
+    if rest.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let aad = associated_data(path);
+
+    ChaCha20Poly1305::new(key)
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &aad,
+            },
+        )
+        .ok()
+}
+
+/// Encrypts `path` to its blob. Returns `false` if the blob already holds the same content.
+pub fn encrypt_file(key: &Key, path: &Path) -> Result<bool, VaultError> {
+    let plaintext = fs::read(path)?;
+    let blob_path = blob_path(path);
+
+    // keep existing blobs to avoid churn, as every encryption uses a fresh nonce.
+    if let Ok(blob) = fs::read(&blob_path) {
+        if decrypt(key, path, &blob).is_some_and(|existing| existing == plaintext) {
+            return Ok(false);
+        }
+    }
+
+    fs::write(&blob_path, encrypt(key, path, &plaintext))?;
+    Ok(true)
+}
+
+/// Decrypts the blob belonging to the plaintext file at `path`.
+pub fn decrypt_file(key: &Key, path: &Path) -> Result<Vec<u8>, VaultError> {
+    let blob_path = blob_path(path);
+    let blob = fs::read(&blob_path)?;
+    decrypt(key, path, &blob).ok_or(VaultError::Corrupted(blob_path))
+}
+
+/// Reads `path` from its blob if the plaintext file does not exist.
+pub fn read_to_string(path: &Path) -> Result<String, VaultError> {
+    let key = load_key()?;
+    let plaintext = decrypt_file(&key, path)?;
+    String::from_utf8(plaintext).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use crate::template::data_dir;
+
+    use chacha20poly1305::{aead::OsRng, ChaCha20Poly1305, KeyInit};
+
+    use super::{blob_path, decode_key, decrypt, encode_key, encrypt};
+
+    #[test]
+    fn roundtrips_content() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let path = Path::new("data/inputs/01.txt");
+        let blob = encrypt(&key, path, b"(()))");
+        assert_eq!(decrypt(&key, path, &blob).unwrap(), b"(()))");
+    }
+
+    #[test]
+    fn rejects_other_file_names() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let blob = encrypt(&key, Path::new("data/inputs/01.txt"), b"(()))");
+        assert!(decrypt(&key, Path::new("data/inputs/02.txt"), &blob).is_none());
+    }
+
+    #[test]
+    fn rejects_other_keys() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let other = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let path = Path::new("data/inputs/01.txt");
+        let blob = encrypt(&key, path, b"(()))");
+        assert!(decrypt(&other, path, &blob).is_none());
+    }
+
+    #[test]
+    fn roundtrips_keys() {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        assert_eq!(decode_key(&encode_key(&key)).unwrap(), key);
+        assert!(decode_key("abc").is_err());
+    }
+
+    #[test]
+    fn appends_blob_extension() {
+        assert_eq!(
+            blob_path(Path::new("data/puzzles/01.md")),
+            Path::new("data/puzzles/01.md.vault")
+        );
+    }
+}
@@ -0,0 +1,276 @@
+/// Polling file watcher that re-runs a day's tests and solution on change.
+/// Polling is used over inotify & co. so this works on any platform without extra dependencies.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::run_multi::child_commands;
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// A file or directory to watch. Directories are watched recursively.
+enum WatchTarget {
+    Path(PathBuf),
+    /// Files in `dir` whose name starts with `prefix`, e.g. `data/examples/01-2.txt`.
+    Prefixed {
+        dir: PathBuf,
+        prefix: String,
+    },
+}
+
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+struct PollWatcher {
+    targets: Vec<WatchTarget>,
+    snapshot: Snapshot,
+}
+
+impl PollWatcher {
+    fn new(targets: Vec<WatchTarget>) -> Self {
+        let mut watcher = Self {
+            targets,
+            snapshot: HashMap::new(),
+        };
+        watcher.snapshot = watcher.scan();
+        watcher
+    }
+
+    fn scan(&self) -> Snapshot {
+        let mut snapshot = HashMap::new();
+        for target in &self.targets {
+            match target {
+                WatchTarget::Path(path) => collect_files(path, &mut snapshot),
+                WatchTarget::Prefixed { dir, prefix } => {
+                    let Ok(entries) = fs::read_dir(dir) else {
+                        continue;
+                    };
+                    entries
+                        .filter_map(Result::ok)
+                        .filter(|e| e.file_name().to_string_lossy().starts_with(prefix.as_str()))
+                        .for_each(|e| collect_files(&e.path(), &mut snapshot));
+                }
+            }
+        }
+        snapshot
+    }
+
+    /// Blocks until a watched file was added, removed or modified.
+    fn wait_for_change(&mut self) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let snapshot = self.scan();
+            if snapshot != self.snapshot {
+                // editors often write files in several steps, wait for them to settle.
+                thread::sleep(POLL_INTERVAL);
+                self.snapshot = self.scan();
+                return;
+            }
+        }
+    }
+}
+
+fn collect_files(path: &Path, snapshot: &mut Snapshot) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            entries
+                .filter_map(Result::ok)
+                .for_each(|e| collect_files(&e.path(), snapshot));
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+fn watch_targets(day: Day) -> Vec<WatchTarget> {
+    vec![
+        WatchTarget::Path(format!("src/bin/{day}.rs").into()),
+        WatchTarget::Path("src/lib.rs".into()),
+        WatchTarget::Path("src/template".into()),
+        WatchTarget::Prefixed {
+            dir: "data/inputs".into(),
+            prefix: day.to_string(),
+        },
+        WatchTarget::Prefixed {
+            dir: "data/examples".into(),
+            prefix: day.to_string(),
+        },
+    ]
+}
+
+/// Runs the day's tests and solution, then again every time one of its sources or inputs changes.
+pub fn handle(day: Day, is_release: bool) {
+    let mut watcher = PollWatcher::new(watch_targets(day));
+    let mut previous: Option<Vec<(u8, String)>> = None;
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, press Ctrl+C to exit){ANSI_RESET}");
+        println!("------");
+
+        run_tests(day);
+        println!();
+
+        let answers = match child_commands::run_solution(day, false, is_release, false, false) {
+            Ok(output) => parse_answers(&output),
+            Err(e) => {
+                eprintln!("Failed to run solution: {e:?}");
+                vec![]
+            }
+        };
+
+        if let Some(previous) = &previous {
+            println!();
+            println!("{ANSI_BOLD}Changes{ANSI_RESET}");
+            print_diff(previous, &answers);
+        }
+
+        previous = Some(answers);
+        watcher.wait_for_change();
+    }
+}
+
+fn run_tests(day: Day) {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => println!("{ANSI_BOLD}Tests:{ANSI_RESET} ✔ passed"),
+        Ok(_) => println!("{ANSI_BOLD}Tests:{ANSI_RESET} ✖ failed"),
+        Err(e) => eprintln!("Failed to run tests: {e}"),
+    }
+}
+
+fn print_diff(previous: &[(u8, String)], current: &[(u8, String)]) {
+    for part in [1, 2] {
+        let before = previous.iter().find(|(p, _)| *p == part).map(|(_, a)| a);
+        let after = current.iter().find(|(p, _)| *p == part).map(|(_, a)| a);
+
+        match (before, after) {
+            (Some(before), Some(after)) if before == after => {
+                println!("Part {part}: {after} {ANSI_ITALIC}(unchanged){ANSI_RESET}");
+            }
+            (Some(before), Some(after)) => {
+                println!("Part {part}: {before} → {ANSI_BOLD}{after}{ANSI_RESET}");
+            }
+            (None, Some(after)) => {
+                println!(
+                    "Part {part}: {ANSI_BOLD}{after}{ANSI_RESET} {ANSI_ITALIC}(new){ANSI_RESET}"
+                );
+            }
+            (Some(before), None) => {
+                println!("Part {part}: {before} → ✖");
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Extracts the answers from the output of a solution binary.
+/// Parts without an answer (✖) are omitted, multi-line answers (▼) are joined with `\n`.
+fn parse_answers(output: &[String]) -> Vec<(u8, String)> {
+    let mut answers: Vec<(u8, String)> = vec![];
+    let mut multiline: Option<(u8, Vec<String>)> = None;
+
+    for line in output {
+        // intermediate results are overwritten with a carriage return, keep the final one.
+        let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+
+        let part = line
+            .strip_prefix("Part ")
+            .and_then(|rest| rest.split_once(": "))
+            .and_then(|(part, rest)| Some((part.parse::<u8>().ok()?, rest)));
+
+        let Some((part, rest)) = part else {
+            if let Some((_, lines)) = &mut multiline {
+                lines.push(line);
+            }
+            continue;
+        };
+
+        if let Some((part, lines)) = multiline.take() {
+            answers.push((part, lines.join("\n").trim_end().to_string()));
+        }
+
+        if rest.starts_with('▼') {
+            multiline = Some((part, vec![]));
+        } else if !rest.starts_with('✖') {
+            let answer = match rest.rfind(" (") {
+                Some(i) if rest.ends_with(')') => &rest[..i],
+                _ => rest,
+            };
+            answers.push((part, answer.to_string()));
+        }
+    }
+
+    if let Some((part, lines)) = multiline {
+        answers.push((part, lines.join("\n").trim_end().to_string()));
+    }
+
+    answers
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the control sequence up to and including its final letter.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_answers;
+
+    #[test]
+    fn parses_answers() {
+        let output = [
+            "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)".into(),
+            "Part 2: ✖\rPart 2: ✖             ".into(),
+        ];
+        assert_eq!(parse_answers(&output), vec![(1, "42".to_string())]);
+    }
+
+    #[test]
+    fn parses_answers_with_parentheses() {
+        let output = ["Part 1: (1, 2) (3.0µs)".into()];
+        assert_eq!(parse_answers(&output), vec![(1, "(1, 2)".to_string())]);
+    }
+
+    #[test]
+    fn parses_multiline_answers() {
+        let output = [
+            "Part 1: ▼ \rPart 1: ▼  (1.0ms)".into(),
+            "#..#".into(),
+            ".##.".into(),
+            "Part 2: \x1b[1m7\x1b[0m (2.0ms)".into(),
+        ];
+        assert_eq!(
+            parse_answers(&output),
+            vec![(1, "#..#\n.##.".to_string()), (2, "7".to_string())]
+        );
+    }
+}